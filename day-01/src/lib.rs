@@ -0,0 +1,226 @@
+/*
+The captcha requires you to review a sequence of digits (your puzzle input) and find the sum of all
+digits that match the digit `offset` steps forward in the list. The list is circular, so the digit
+after the last digit is the first digit in the list.
+
+Part 1 uses an offset of 1:
+
+    1122 produces a sum of 3 (1 + 2) because the first digit (1) matches the second digit and the third digit (2) matches the fourth digit.
+    1111 produces 4 because each digit (all 1) matches the next.
+    1234 produces 0 because no digit matches the next.
+    91212129 produces 9 because the only digit that matches the next one is the last digit, 9.
+
+Part 2 uses an offset of halfway around the list:
+
+    1212 produces 6: the list contains 4 items, and all four digits match the digit 2 items ahead.
+    1221 produces 0, because every comparison is between a 1 and a 2.
+    123425 produces 4, because both 2s match each other, but no other digit has a match.
+    123123 produces 12.
+    12131415 produces 4.
+*/
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::Read;
+
+/// How far ahead in the list each digit is compared against
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Offset {
+    /// A fixed number of steps forward
+    Steps(usize),
+
+    /// Half the length of the list
+    Halfway,
+}
+
+impl From<usize> for Offset {
+    fn from(steps: usize) -> Self { Offset::Steps(steps) }
+}
+
+/// The reasons a captcha can not be solved
+#[derive(Debug)]
+pub enum CaptchaError {
+    /// A character which is neither a digit nor whitespace, along with its byte offset in the
+    /// input (unlike the indexes of matches, this counts whitespace)
+    InvalidCharacter(char, usize),
+
+    /// The input could not be read
+    Io(io::Error),
+}
+
+impl fmt::Display for CaptchaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CaptchaError::InvalidCharacter(c, position) =>
+                write!(f, "`{}` at position {} is not a digit", c, position),
+            CaptchaError::Io(ref err) =>
+                write!(f, "Unable to read input: {}", err),
+        }
+    }
+}
+
+impl Error for CaptchaError {}
+
+impl From<io::Error> for CaptchaError {
+    fn from(err: io::Error) -> Self { CaptchaError::Io(err) }
+}
+
+/// A solved captcha
+#[derive(Debug, PartialEq)]
+pub struct Captcha {
+    /// The sum of all digits which matched
+    pub sum: u64,
+
+    /// The index (ignoring whitespace) of each digit which matched the digit `offset` ahead of it
+    pub matches: Vec<usize>,
+}
+
+/// Reads all digits from the input, skipping any whitespace
+fn read_digits<R: Read>(mut input: R) -> Result<Vec<u8>, CaptchaError> {
+    let mut buffer = [0; 64 * 1024];
+    let mut digits = vec![];
+    let mut position = 0;
+
+    loop {
+        let read = match input.read(&mut buffer) {
+            Ok(0)    => break,
+            Ok(read) => read,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(CaptchaError::from(err)),
+        };
+
+        for &byte in &buffer[.. read] {
+            match byte {
+                b'0' ..= b'9' => digits.push(byte - b'0'),
+                _ if byte.is_ascii_whitespace() => (),
+                _ => {
+                    let c = if byte.is_ascii() { byte as char } else { char::REPLACEMENT_CHARACTER };
+                    return Err(CaptchaError::InvalidCharacter(c, position));
+                },
+            }
+
+            position += 1;
+        }
+    }
+
+    Ok(digits)
+}
+
+/// Solves the captcha for the given stream of `digits`, comparing each digit against the one
+/// `offset` steps ahead of it in the circular list
+///
+/// # Example
+///
+/// `captcha("1122".as_bytes(), 1)` has a sum of `3`, with matches at `[0, 2]`
+pub fn captcha<R: Read, O: Into<Offset>>(digits: R, offset: O) -> Result<Captcha, CaptchaError> {
+    Ok(solve(&read_digits(digits)?, offset.into()))
+}
+
+/// Solves the captcha for each of the `offsets`, reading the stream of `digits` only once
+pub fn captchas<R: Read>(digits: R, offsets: &[Offset]) -> Result<Vec<Captcha>, CaptchaError> {
+    let digits = read_digits(digits)?;
+
+    Ok(offsets.iter().map(| &offset | solve(&digits, offset)).collect())
+}
+
+fn solve(digits: &[u8], offset: Offset) -> Captcha {
+    let len = digits.len();
+
+    if len == 0 {
+        return Captcha { sum: 0, matches: vec![] };
+    }
+
+    let offset = match offset {
+        Offset::Steps(steps) => steps % len,
+        Offset::Halfway      => len / 2,
+    };
+
+    let mut sum = 0;
+    let mut matches = vec![];
+
+    for (i, &digit) in digits.iter().enumerate() {
+        if digit == digits[(i + offset) % len] {
+            sum += u64::from(digit);
+            matches.push(i);
+        }
+    }
+
+    Captcha { sum, matches }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sum(input: &str, offset: Offset) -> u64 {
+        captcha(input.as_bytes(), offset).unwrap().sum
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(sum("1122", Offset::Steps(1)), 3);
+        assert_eq!(sum("1111", Offset::Steps(1)), 4);
+        assert_eq!(sum("1234", Offset::Steps(1)), 0);
+        assert_eq!(sum("91212129", Offset::Steps(1)), 9);
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(sum("1212", Offset::Halfway), 6);
+        assert_eq!(sum("1221", Offset::Halfway), 0);
+        assert_eq!(sum("123425", Offset::Halfway), 4);
+        assert_eq!(sum("123123", Offset::Halfway), 12);
+        assert_eq!(sum("12131415", Offset::Halfway), 4);
+    }
+
+    #[test]
+    fn test_matches() {
+        assert_eq!(
+            captcha("1122\n".as_bytes(), 1).unwrap(),
+            Captcha { sum: 3, matches: vec![0, 2] }
+        );
+
+        // Offsets larger than the list wrap around
+        assert_eq!(
+            captcha("91212129".as_bytes(), 9).unwrap(),
+            Captcha { sum: 9, matches: vec![7] }
+        );
+
+        assert_eq!(
+            captcha("".as_bytes(), 1).unwrap(),
+            Captcha { sum: 0, matches: vec![] }
+        );
+    }
+
+    #[test]
+    fn test_invalid_character() {
+        match captcha("12a4".as_bytes(), 1) {
+            Err(CaptchaError::InvalidCharacter('a', 2)) => (),
+            other => panic!("Expected invalid character error, got {:?}", other),
+        }
+
+        // Whitespace is counted in the position
+        match captcha("12\n 4!".as_bytes(), 1) {
+            Err(CaptchaError::InvalidCharacter('!', 5)) => (),
+            other => panic!("Expected invalid character error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_captchas() {
+        assert_eq!(
+            captchas("1212".as_bytes(), &[Offset::Steps(1), Offset::Halfway]).unwrap(),
+            vec![Captcha { sum: 0, matches: vec![] }, Captcha { sum: 6, matches: vec![0, 1, 2, 3] }]
+        );
+    }
+
+    #[test]
+    fn test_large_stream() {
+        let input = io::repeat(b'7').take(5_000_000);
+        let result = captcha(input, 1_234_567).unwrap();
+
+        assert_eq!(result.sum, 35_000_000);
+        assert_eq!(result.matches.len(), 5_000_000);
+    }
+}
@@ -0,0 +1,22 @@
+extern crate day_01;
+
+use std::io;
+
+use day_01::*;
+
+fn main() {
+    println!("Please enter the input: ");
+
+    let stdin = io::stdin();
+
+    match captchas(stdin.lock(), &[Offset::Steps(1), Offset::Halfway]) {
+        Ok(captchas) => {
+            println!("Part 1: {}", captchas[0].sum);
+            println!("Part 2: {}", captchas[1].sum);
+        },
+        Err(err) => {
+            eprintln!("Invalid input: {}", err);
+            std::process::exit(1);
+        },
+    }
+}
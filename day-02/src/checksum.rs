@@ -0,0 +1,271 @@
+/// A strategy for calculating the checksum of a single spreadsheet row
+///
+/// Any `Fn(&[u32]) -> Result<u32, String>` can be used as a strategy.
+pub trait RowChecksum {
+    /// Calculates the checksum of the `row`, or explains why it doesn't have one
+    fn checksum(&self, row: &[u32]) -> Result<u32, String>;
+}
+
+impl<F> RowChecksum for F where F: Fn(&[u32]) -> Result<u32, String> {
+    fn checksum(&self, row: &[u32]) -> Result<u32, String> {
+        self(row)
+    }
+}
+
+/// The difference between the largest value and the smallest value in the row
+///
+/// # Example
+///
+/// ```text
+/// 5 1 9 5
+/// ```
+///
+/// The largest and smallest values are 9 and 1, so the checksum is 8.
+pub struct MaxMinDifference;
+
+impl RowChecksum for MaxMinDifference {
+    fn checksum(&self, row: &[u32]) -> Result<u32, String> {
+        match (row.iter().max(), row.iter().min()) {
+            (Some(max), Some(min)) => Ok(max - min),
+            _                      => Err(String::from("No data on row")),
+        }
+    }
+}
+
+/// The result of dividing the only two numbers in the row where one evenly divides the other
+///
+/// # Example
+///
+/// ```text
+/// 5 9 2 8
+/// ```
+///
+/// The only two numbers that evenly divide are 8 and 2, so the checksum is 4.
+///
+/// If more than one pair divides, the pair whose columns come first in the row is used. The pairs
+/// are found with `divisible_pairs`, so wide rows aren't compared pair by pair.
+pub struct EvenlyDivisible;
+
+impl RowChecksum for EvenlyDivisible {
+    fn checksum(&self, row: &[u32]) -> Result<u32, String> {
+        use std::collections::HashMap;
+
+        // The first two columns each value appears in
+        let mut columns: HashMap<u32, (usize, Option<usize>)> = HashMap::new();
+
+        for (column, &value) in row.iter().enumerate() {
+            columns.entry(value)
+                .and_modify(| seen | if seen.1.is_none() { seen.1 = Some(column) })
+                .or_insert((column, None));
+        }
+
+        divisible_pairs(row).into_iter()
+            .min_by_key(| &(dividend, divisor) | {
+                let (a, b) = (columns[&dividend], columns[&divisor]);
+
+                if dividend == divisor {
+                    (a.0, a.1.expect("Only repeated values are paired with themselves"))
+                } else {
+                    (a.0.min(b.0), a.0.max(b.0))
+                }
+            })
+            .map(| (dividend, divisor) | dividend / divisor)
+            .ok_or_else(|| format!("No divisible data found on row: {:?}", row))
+    }
+}
+
+/// Finds every pair of values in the row where one evenly divides the other, as
+/// `(dividend, divisor)` sorted in ascending order. Values appearing more than once only have
+/// their pairs listed once.
+///
+/// The values are sorted, then for each distinct divisor `d` we either look up each of it's
+/// multiples up to the largest value, or check each larger value in the row, whichever is fewer.
+/// So wide rows of small numbers do at most `max / d` lookups per value rather than comparing
+/// every pair.
+///
+/// # Example
+///
+/// `[3, 12, 5, 6]` gives `[(6, 3), (12, 3), (12, 6)]`
+pub fn divisible_pairs(row: &[u32]) -> Vec<(u32, u32)> {
+    use std::collections::HashSet;
+
+    let mut values = row.to_vec();
+    values.sort_unstable();
+
+    let mut pairs = vec![];
+
+    // Any repeated value evenly divides itself
+    for window in values.windows(2) {
+        if window[0] == window[1] && window[0] != 0 && pairs.last() != Some(&(window[0], window[0])) {
+            pairs.push((window[0], window[0]));
+        }
+    }
+
+    values.dedup();
+
+    let max = match values.last() {
+        Some(&max) => u64::from(max),
+        None       => return pairs,
+    };
+
+    let present: HashSet<u32> = values.iter().cloned().collect();
+
+    for (i, &divisor) in values.iter().enumerate() {
+        let larger = &values[i + 1 ..];
+
+        if divisor == 0 {
+            // Zero is a multiple of everything else
+            pairs.extend(larger.iter().map(| &value | (0, value)));
+        } else if max / u64::from(divisor) <= larger.len() as u64 {
+            let mut multiple = u64::from(divisor) * 2;
+
+            while multiple <= max {
+                if present.contains(&(multiple as u32)) {
+                    pairs.push((multiple as u32, divisor));
+                }
+
+                multiple += u64::from(divisor);
+            }
+        } else {
+            pairs.extend(
+                larger.iter()
+                    .filter(| value | value.is_multiple_of(divisor))
+                    .map(| &value | (value, divisor))
+            );
+        }
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+/// The checksum of each row in a spreadsheet
+#[derive(Debug, PartialEq)]
+pub struct Report {
+    pub rows: Vec<Result<u32, String>>,
+}
+
+impl Report {
+    /// The checksum of the whole spreadsheet, ignoring any rows which had errors
+    pub fn total(&self) -> u32 {
+        self.rows.iter().filter_map(| row | row.as_ref().ok()).sum()
+    }
+
+    /// Returns the index and error message of each row which failed
+    pub fn errors(&self) -> Vec<(usize, &str)> {
+        self.rows.iter()
+            .enumerate()
+            .filter_map(| (i, row) | row.as_ref().err().map(| err | (i, err.as_str())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_max_min_difference() {
+        assert_eq!(MaxMinDifference.checksum(&[5, 1, 9, 5]), Ok(8));
+        assert_eq!(MaxMinDifference.checksum(&[7, 5, 3]), Ok(4));
+        assert_eq!(MaxMinDifference.checksum(&[2, 4, 6, 8]), Ok(6));
+        assert!(MaxMinDifference.checksum(&[]).is_err());
+    }
+
+    #[test]
+    fn test_evenly_divisible() {
+        assert_eq!(EvenlyDivisible.checksum(&[5, 9, 2, 8]), Ok(4));
+        assert_eq!(EvenlyDivisible.checksum(&[9, 4, 7, 3]), Ok(3));
+        assert_eq!(EvenlyDivisible.checksum(&[3, 8, 6, 5]), Ok(2));
+        assert_eq!(EvenlyDivisible.checksum(&[0, 5]), Ok(0));
+        assert!(EvenlyDivisible.checksum(&[3, 5, 7]).is_err());
+    }
+
+    #[test]
+    fn test_ambiguous_row() {
+        // The first pair in the row is used
+        assert_eq!(EvenlyDivisible.checksum(&[8, 2, 4]), Ok(4));
+        assert_eq!(EvenlyDivisible.checksum(&[2, 4, 8]), Ok(2));
+        assert_eq!(EvenlyDivisible.checksum(&[3, 7, 3, 9]), Ok(1));
+        assert_eq!(EvenlyDivisible.checksum(&[3, 9, 7, 3]), Ok(3));
+    }
+
+    /// Compares every pair in the row, returning the first which divides
+    fn first_pair(row: &[u32]) -> Result<u32, String> {
+        for i in 0 .. row.len() {
+            for j in i + 1 .. row.len() {
+                let first  = row[i];
+                let second = row[j];
+
+                if second != 0 && first.is_multiple_of(second) {
+                    return Ok(first / second);
+                } else if first != 0 && second.is_multiple_of(first) {
+                    return Ok(second / first);
+                }
+            }
+        }
+
+        Err(format!("No divisible data found on row: {:?}", row))
+    }
+
+    #[test]
+    fn test_evenly_divisible_matches_pairwise_scan() {
+        let mut seed: u64 = 54321;
+
+        for _ in 0 .. 2000 {
+            let row: Vec<u32> = (0 .. 6).map(| _ | {
+                seed = (seed * 48271) % 2147483647;
+                (seed % 40) as u32
+            }).collect();
+
+            assert_eq!(EvenlyDivisible.checksum(&row), first_pair(&row), "{:?}", row);
+        }
+    }
+
+    #[test]
+    fn test_divisible_pairs() {
+        assert_eq!(divisible_pairs(&[3, 12, 5, 6]), vec![(6, 3), (12, 3), (12, 6)]);
+        assert_eq!(divisible_pairs(&[7, 7, 7, 1]), vec![(7, 1), (7, 7)]);
+        assert_eq!(divisible_pairs(&[0, 0, 4]), vec![(0, 4)]);
+        assert_eq!(divisible_pairs(&[]), vec![]);
+        assert_eq!(divisible_pairs(&[u32::MAX, 1]), vec![(u32::MAX, 1)]);
+    }
+
+    #[test]
+    fn test_divisible_pairs_matches_pairwise_scan() {
+        // A wide row of pseudo-random values, checked against comparing every pair
+        let mut seed: u64 = 12345;
+        let mut row: Vec<u32> = (0 .. 2000).map(| _ | {
+            seed = (seed * 48271) % 2147483647;
+            (seed % 5000) as u32
+        }).collect();
+
+        // Make sure zeros, which are a multiple of everything else, are included
+        row.extend_from_slice(&[0, 0]);
+
+        let mut expected = vec![];
+
+        for i in 0 .. row.len() {
+            for j in 0 .. row.len() {
+                let (dividend, divisor) = (row[i], row[j]);
+
+                if i != j && divisor != 0 && dividend.is_multiple_of(divisor) {
+                    expected.push((dividend, divisor));
+                }
+            }
+        }
+
+        expected.sort_unstable();
+        expected.dedup();
+
+        assert_eq!(divisible_pairs(&row), expected);
+    }
+
+    #[test]
+    fn test_report() {
+        let report = Report { rows: vec![Ok(3), Err(String::from("Bad row")), Ok(4)] };
+
+        assert_eq!(report.total(), 7);
+        assert_eq!(report.errors(), vec![(1, "Bad row")]);
+    }
+}
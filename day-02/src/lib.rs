@@ -0,0 +1,44 @@
+pub mod checksum;
+pub mod spreadsheet;
+
+pub use checksum::{divisible_pairs, EvenlyDivisible, MaxMinDifference, Report, RowChecksum};
+pub use spreadsheet::Spreadsheet;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let spreadsheet: Spreadsheet = "5 1 9 5\n7 5 3\n2 4 6 8".parse().unwrap();
+
+        assert_eq!(spreadsheet.checksum(&MaxMinDifference).total(), 18);
+    }
+
+    #[test]
+    fn part2_example() {
+        let spreadsheet: Spreadsheet = "5 9 2 8\n9 4 7 3\n3 8 6 5".parse().unwrap();
+
+        assert_eq!(spreadsheet.checksum(&EvenlyDivisible).total(), 9);
+    }
+
+    #[test]
+    fn test_row_errors() {
+        let spreadsheet: Spreadsheet = "5 9 2 8\n3 5 7\n3 8 6 5".parse().unwrap();
+        let report = spreadsheet.checksum(&EvenlyDivisible);
+
+        assert_eq!(report.total(), 6);
+        assert_eq!(report.errors(), vec![(1, "No divisible data found on row: [3, 5, 7]")]);
+    }
+
+    #[test]
+    fn test_custom_strategy() {
+        let spreadsheet: Spreadsheet = "1,2,3\n4,5,6".parse().unwrap();
+        let sum_of_row = | row: &[u32] | -> Result<u32, String> { Ok(row.iter().sum()) };
+
+        assert_eq!(
+            spreadsheet.checksum(&sum_of_row),
+            Report { rows: vec![Ok(6), Ok(15)] }
+        );
+    }
+}
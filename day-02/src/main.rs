@@ -0,0 +1,32 @@
+extern crate day_02;
+
+use std::io;
+use std::io::Read;
+
+use day_02::*;
+
+fn main() {
+    println!("Please enter the spreadsheet: ");
+
+    let mut input = String::new();
+
+    io::stdin().read_to_string(&mut input)
+        .expect("Failed to read input");
+
+    let spreadsheet: Spreadsheet = match input.parse() {
+        Ok(spreadsheet) => spreadsheet,
+        Err(err)        => { eprintln!("Invalid spreadsheet: {}", err); std::process::exit(1); },
+    };
+
+    print_report("Part 1", &spreadsheet.checksum(&MaxMinDifference));
+    print_report("Part 2", &spreadsheet.checksum(&EvenlyDivisible));
+}
+
+/// Prints the checksum for a report, along with any rows which were skipped
+fn print_report(name: &str, report: &Report) {
+    println!("{}: {}", name, report.total());
+
+    for (row, err) in report.errors() {
+        eprintln!("{}: Skipped row {}: {}", name, row + 1, err);
+    }
+}
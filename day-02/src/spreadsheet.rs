@@ -0,0 +1,91 @@
+use std::str::FromStr;
+
+use checksum::{Report, RowChecksum};
+
+/// A spreadsheet of numbers
+///
+/// Cells can be separated by any mix of whitespace, tabs or commas, so whitespace separated,
+/// CSV and TSV inputs can all be parsed. Blank lines are ignored, and rows which can't be parsed
+/// are kept as errors so the rest of the spreadsheet can still be used.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spreadsheet {
+    pub rows: Vec<Result<Vec<u32>, String>>,
+}
+
+impl Spreadsheet {
+    /// Calculates the checksum of every row using the given `strategy`, passing on the errors of
+    /// rows which couldn't be parsed
+    pub fn checksum<C: RowChecksum>(&self, strategy: &C) -> Report {
+        Report {
+            rows: self.rows.iter()
+                .map(| row | row.as_ref().map_err(String::clone).and_then(| row | strategy.checksum(row)))
+                .collect()
+        }
+    }
+}
+
+/// Parses a single row of the spreadsheet
+fn parse_row(line: &str, line_no: usize) -> Result<Vec<u32>, String> {
+    line.split(| c: char | c == ',' || c.is_whitespace())
+        .filter(| col | !col.is_empty())
+        .map(| col | col.parse().map_err(
+            | e | format!("`{}` on line {} was not an integer: {}", col, line_no + 1, e)
+        ))
+        .collect()
+}
+
+impl FromStr for Spreadsheet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s.lines()
+            .enumerate()
+            .filter(| &(_, line) | !line.trim().is_empty())
+            .map(| (line_no, line) | parse_row(line, line_no))
+            .collect();
+
+        Ok(Spreadsheet { rows })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use checksum::MaxMinDifference;
+
+    #[test]
+    fn test_parse() {
+        let expected = Spreadsheet { rows: vec![Ok(vec![5, 1, 9, 5]), Ok(vec![7, 5, 3])] };
+
+        assert_eq!("5 1 9 5\n7 5 3".parse(), Ok(expected.clone()));
+        assert_eq!("5,1,9,5\n\n7,5,3\n".parse(), Ok(expected.clone()));
+        assert_eq!("5\t1\t9\t5\r\n7\t5\t3\r\n".parse(), Ok(expected.clone()));
+        assert_eq!("5, 1, 9, 5\n7 ,5 ,3".parse(), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let spreadsheet: Spreadsheet = "1 2\n3 x\n\n4 -5\n6 8".parse().unwrap();
+
+        assert_eq!(
+            spreadsheet.rows,
+            vec![
+                Ok(vec![1, 2]),
+                Err(String::from("`x` on line 2 was not an integer: invalid digit found in string")),
+                Err(String::from("`-5` on line 4 was not an integer: invalid digit found in string")),
+                Ok(vec![6, 8]),
+            ]
+        );
+
+        let report = spreadsheet.checksum(&MaxMinDifference);
+
+        assert_eq!(report.total(), 3);
+        assert_eq!(
+            report.errors(),
+            vec![
+                (1, "`x` on line 2 was not an integer: invalid digit found in string"),
+                (2, "`-5` on line 4 was not an integer: invalid digit found in string"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,19 @@
+extern crate day_03;
+extern crate utils;
+
+use day_03::*;
+use day_03::render::*;
+
+/// Draws the spiral out to the ring containing the input square, highlighting it's path back to
+/// the centre
+fn main() {
+    let input: i64 = utils::read_input();
+
+    let radius = match index_to_position(input) {
+        Some(position) => position.x.abs().max(position.y.abs()),
+        None           => { eprintln!("Square must be at least 1"); return; },
+    };
+
+    println!("Indices:\n{}", render_indices(radius, Some(input)));
+    println!("Neighbour sums:\n{}", render_sums(radius, Some(input)));
+}
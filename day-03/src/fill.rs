@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use utils::grid::Position;
+use SpiralIter;
+
+/// Which of the surrounding squares are used as neighbours when filling the spiral
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Neighbourhood {
+    /// Only the squares above, below, left and right
+    Four,
+
+    /// All eight surrounding squares, including diagonals
+    Eight,
+}
+
+/// Fills the spiral one square at a time, setting each square to the result of a `reducer` over
+/// the values of it's neighbours which have already been filled
+///
+/// The value type is generic, so a `BigUint` can be used for long sequences which would overflow
+/// the primitive integers.
+///
+/// Starting from `1` and summing all eight neighbours gives the part 2 sequence,
+/// [OEIS A141481](https://oeis.org/A141481).
+pub struct SpiralFill<T, F> {
+    positions: SpiralIter,
+    grid: HashMap<Position, T>,
+    first: Option<T>,
+    neighbourhood: Neighbourhood,
+    reducer: F,
+}
+
+impl<T, F> SpiralFill<T, F> where T: Clone, F: Fn(&[T]) -> T {
+    /// Starts a new spiral with `first` in the centre square
+    pub fn new(first: T, neighbourhood: Neighbourhood, reducer: F) -> Self {
+        SpiralFill {
+            positions: SpiralIter::default(),
+            grid: HashMap::new(),
+            first: Some(first),
+            neighbourhood,
+            reducer,
+        }
+    }
+
+    /// The values of the neighbours of `position` which have already been filled
+    fn neighbour_values(&self, position: &Position) -> Vec<T> {
+        let lookup = | pos: &Position | self.grid.get(pos).cloned();
+
+        match self.neighbourhood {
+            Neighbourhood::Four  => position.orthogonal_neighbours().iter().filter_map(lookup).collect(),
+            Neighbourhood::Eight => position.neighbours().iter().filter_map(lookup).collect(),
+        }
+    }
+}
+
+impl<T, F> Iterator for SpiralFill<T, F> where T: Clone, F: Fn(&[T]) -> T {
+    type Item = (Position, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.positions.next()?;
+
+        let value = match self.first.take() {
+            Some(first) => first,
+            None        => (self.reducer)(&self.neighbour_values(&position)),
+        };
+
+        self.grid.insert(position, value.clone());
+
+        Some((position, value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_neighbour_sums() {
+        let values: Vec<u32> =
+            SpiralFill::new(1, Neighbourhood::Eight, | v: &[u32] | v.iter().sum())
+                .take(23)
+                .map(| (_, value) | value)
+                .collect();
+
+        assert_eq!(
+            values,
+            vec![
+                1, 1, 2, 4, 5, 10, 11, 23, 25, 26, 54, 57, 59, 122, 133, 142, 147, 304, 330, 351,
+                362, 747, 806
+            ]
+        );
+    }
+
+    #[test]
+    fn test_positions() {
+        let squares: Vec<(i64, i64, u32)> =
+            SpiralFill::new(1, Neighbourhood::Eight, | v: &[u32] | v.iter().sum())
+                .take(4)
+                .map(| (p, value) | (p.x, p.y, value))
+                .collect();
+
+        assert_eq!(squares, vec![(0, 0, 1), (1, 0, 1), (1, 1, 2), (0, 1, 4)]);
+    }
+
+    #[test]
+    fn test_four_neighbours() {
+        let values: Vec<u32> =
+            SpiralFill::new(1, Neighbourhood::Four, | v: &[u32] | v.iter().sum())
+                .take(10)
+                .map(| (_, value) | value)
+                .collect();
+
+        assert_eq!(values, vec![1, 1, 1, 2, 2, 3, 3, 4, 5, 5]);
+    }
+
+    #[test]
+    fn test_other_reducers() {
+        let maxes: Vec<u32> =
+            SpiralFill::new(1, Neighbourhood::Eight, | v: &[u32] | v.iter().max().unwrap() + 1)
+                .take(6)
+                .map(| (_, value) | value)
+                .collect();
+
+        assert_eq!(maxes, vec![1, 2, 3, 4, 5, 6]);
+
+        let products: Vec<u32> =
+            SpiralFill::new(2, Neighbourhood::Eight, | v: &[u32] | v.iter().product())
+                .take(5)
+                .map(| (_, value) | value)
+                .collect();
+
+        assert_eq!(products, vec![2, 2, 4, 16, 32]);
+    }
+
+    #[test]
+    fn test_big_integers() {
+        let small = SpiralFill::new(1u128, Neighbourhood::Eight, | v: &[u128] | v.iter().sum());
+        let big = SpiralFill::new(BigUint::from(1u32), Neighbourhood::Eight, | v: &[BigUint] | v.iter().sum());
+
+        let (_, last) = small.zip(big)
+            .take(1000)
+            .inspect(| &((_, ref s), (_, ref b)) | assert_eq!(s.to_string(), b.to_string()))
+            .last()
+            .unwrap();
+
+        assert!(last.1 > BigUint::from(u64::MAX));
+    }
+}
@@ -1,88 +1,161 @@
-/// A simple struct to track the position
-#[derive(Hash, Eq, PartialEq, Default, Clone)]
-pub struct Position {
-    x: i32,
-    y: i32,
+extern crate num_bigint;
+extern crate utils;
+
+pub mod fill;
+pub mod render;
+
+pub use fill::{Neighbourhood, SpiralFill};
+pub use utils::grid::{Direction, Position};
+
+/// The direction to move from `position` to reach the next square of the spiral
+///
+/// The spiral winds anti-clockwise, so each ring is entered from the bottom right corner of the
+/// previous one, then travels up, left, down and back along the bottom.
+pub fn spiral_direction(position: &Position) -> Direction {
+    let k = position.x.abs().max(position.y.abs());
+
+    if position.y == -k {
+        Direction::Right
+    } else if position.x == -k {
+        Direction::Down
+    } else if position.y == k {
+        Direction::Left
+    } else {
+        Direction::Up
+    }
 }
 
-/// A simple enum to track our current direction
-pub enum Direction {
-    LEFT,
-    RIGHT,
-    DOWN,
-    UP,
+/// Iterates over every position in the spiral in order, starting with square `1` at the origin
+///
+/// ```text
+/// 5   4   3
+/// 6   1   2
+/// 7   8   9  10 ...
+/// ```
+#[derive(Default)]
+pub struct SpiralIter {
+    next: Position,
 }
 
-impl Default for Direction {
-    fn default() -> Self { Direction::RIGHT }
+impl Iterator for SpiralIter {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next;
+        self.next = current.step(spiral_direction(&current));
+
+        Some(current)
+    }
 }
 
-impl Position {
-    /// Creates a new position, moving around the spiral grid
-    pub fn mov(&self, direction: &mut Direction, grid_size: &mut i32) -> Position {
-        match *direction {
-            Direction::LEFT  => {
-                if self.x == -*grid_size {
-                    *direction = Direction::DOWN;
-                    self.down()
-                } else {
-                    self.left()
-                }
-            },
-            Direction::RIGHT => {
-                if self.x == *grid_size {
-                    *direction = Direction::UP;
-                    *grid_size = *grid_size + 1;
-                    self.up()
-                } else {
-                    self.right()
-                }
-            },
-            Direction::DOWN  => {
-                if self.y == -*grid_size {
-                    *direction = Direction::RIGHT;
-                    self.right()
-                } else {
-                    self.down()
-                }
-            },
-            Direction::UP    => {
-                if self.y == *grid_size {
-                    *direction = Direction::LEFT;
-                    self.left()
-                } else {
-                    self.up()
-                }
-            },
-        }
+/// Finds the position of square `n` in the spiral, or `None` if `n` is less than 1
+///
+/// Square `n` is on ring `k` (the ring of side `2k + 1`) where `(2k - 1)^2 < n <= (2k + 1)^2`. The
+/// largest square of each ring is in the bottom right corner, so we walk back from there along
+/// each side in turn.
+pub fn index_to_position(n: i64) -> Option<Position> {
+    if n < 1 {
+        return None;
+    }
+
+    // Work in i128 so the corners of the outer most rings can't overflow
+    let n = i128::from(n);
+
+    let mut side = (n - 1).isqrt() + 1;
+    if side % 2 == 0 {
+        side += 1;
     }
 
-    pub fn neighbours(&self) -> [Position; 8] {
-        [
-            self.left(),
-            self.left().up(),
-            self.up(),
-            self.right().up(),
-            self.right(),
-            self.right().down(),
-            self.down(),
-            self.left().down()
-        ]
+    let k = (side - 1) / 2;
+    let edge = side - 1;
+    let max = side * side;
+
+    let (x, y) =
+        if n >= max - edge {
+            (k - (max - n), -k)                     // Bottom, going left from (k, -k)
+        } else if n >= max - 2 * edge {
+            (-k, -k + (max - edge - n))             // Left, going up from (-k, -k)
+        } else if n >= max - 3 * edge {
+            (-k + (max - 2 * edge - n), k)          // Top, going right from (-k, k)
+        } else {
+            (k, k - (max - 3 * edge - n))           // Right, going down from (k, k)
+        };
+
+    Some(Position::new(x as i64, y as i64))
+}
+
+/// Finds which square is at the given position in the spiral, or `None` if the square number is
+/// too large to fit in an `i64`
+pub fn position_to_index(x: i64, y: i64) -> Option<i64> {
+    let (x, y) = (i128::from(x), i128::from(y));
+
+    let k = x.abs().max(y.abs());
+
+    // Every square on rings this far out is larger than `i64::MAX`
+    if k > 1 << 32 {
+        return None;
     }
 
-    fn left(&self) -> Position {
-        Position { x: self.x - 1, y: self. y}
+    let max = (2 * k + 1) * (2 * k + 1);
+
+    let n =
+        if y == -k {
+            max - (k - x)
+        } else if x == -k {
+            max - 2 * k - (y + k)
+        } else if y == k {
+            max - 4 * k - (x + k)
+        } else {
+            max - 6 * k - (k - y)
+        };
+
+    if n > i128::from(i64::MAX) { None } else { Some(n as i64) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_spiral_iter() {
+        let positions: Vec<(i64, i64)> = SpiralIter::default().take(10).map(| p | (p.x, p.y)).collect();
+
+        assert_eq!(
+            positions,
+            vec![(0, 0), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1), (2, -1)]
+        );
     }
 
-    fn right(&self) -> Position {
-        Position { x: self.x + 1, y: self. y}
+    #[test]
+    fn test_index_to_position() {
+        assert_eq!(index_to_position(0), None);
+        assert_eq!(index_to_position(1), Some(Position::new(0, 0)));
+        assert_eq!(index_to_position(12), Some(Position::new(2, 1)));
+        assert_eq!(index_to_position(17), Some(Position::new(-2, 2)));
+        assert_eq!(index_to_position(21), Some(Position::new(-2, -2)));
+        assert_eq!(index_to_position(23), Some(Position::new(0, -2)));
+        assert_eq!(index_to_position(25), Some(Position::new(2, -2)));
     }
 
-    fn down(&self) -> Position {
-        Position { x: self.x, y: self. y - 1}
+    #[test]
+    fn test_matches_walking_the_spiral() {
+        for (i, position) in SpiralIter::default().take(10_000).enumerate() {
+            let n = i as i64 + 1;
+
+            assert_eq!(index_to_position(n), Some(position), "Square {}", n);
+            assert_eq!(position_to_index(position.x, position.y), Some(n), "Square {}", n);
+        }
     }
 
-    fn up(&self) -> Position {
-        Position { x: self.x, y: self. y + 1}
+    #[test]
+    fn test_large_values() {
+        for &n in &[i64::MAX, i64::MAX - 1, 1 << 62, 4_611_686_014_132_420_609] {
+            let position = index_to_position(n).unwrap();
+
+            assert_eq!(position_to_index(position.x, position.y), Some(n), "Square {}", n);
+        }
+
+        assert_eq!(position_to_index(i64::MAX, 0), None);
+        assert_eq!(position_to_index(0, i64::MIN), None);
     }
-}
\ No newline at end of file
+}
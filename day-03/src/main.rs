@@ -1,9 +1,7 @@
+extern crate day_03;
 extern crate utils;
 
-mod lib;
-use lib::*;
-
-use std::collections::HashMap;
+use day_03::*;
 
 #[cfg(not(test))]
 fn main() {
@@ -27,28 +25,10 @@ fn main() {
 ///
 /// Input of `5` will return `10`
 fn sum(input: i32) -> i32 {
-    let mut position = Position::default();
-    let mut direction = Direction::default();
-    let mut grid_size= 0;
-
-    let mut grid = HashMap::new();
-    grid.insert(position.clone(), 1);
-
-    loop {
-        position = position.mov(&mut direction, &mut grid_size);
-
-        let sum = position.neighbours().iter()
-            .map(| pos | grid.get(pos))
-            .filter( | opt | opt.is_some() )
-            .map( | opt | opt.unwrap() )
-            .sum();
-
-        if sum > input {
-            return sum;
-        } else {
-            grid.insert(position.clone(), sum);
-        }
-    }
+    SpiralFill::new(1, Neighbourhood::Eight, | values: &[i32] | values.iter().sum())
+        .map(| (_, value) | value)
+        .find(| value | *value > input)
+        .unwrap()
 }
 
 /// Calculates the grid distance needed to travel in an
@@ -66,14 +46,9 @@ fn sum(input: i32) -> i32 {
 ///
 /// The distance to travel to the centre from `22` is `Up 2`, `Right 1` which is a distance of `3`
 fn distance(n: i32) -> i32 {
-    let k  = (((n as f32).sqrt() - 1.) / 2.).ceil() as i32;
-    let t = 2 * k + 1;
-    let mut m  = t.pow(2);
-    let t  = t - 1;
+    let position = index_to_position(i64::from(n)).expect("Square must be at least 1");
 
-    if n >= m - t { return (k - (m - n)).abs() + (-k).abs() } else { m = m -t }
-    if n >= m - t { return (-k).abs() + (-k + (m - n)).abs() } else { m = m -t }
-    if n >= m - t { return (-k + (m - n)).abs() + (k).abs() } else { return (k).abs() + (k - (m - n - t)).abs() }
+    (position.x.abs() + position.y.abs()) as i32
 }
 
 // Conditionally compile the module `test` only when the test-suite is run.
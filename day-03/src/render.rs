@@ -0,0 +1,174 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use num_bigint::BigUint;
+use utils::grid::Position;
+use {index_to_position, Neighbourhood, SpiralFill, SpiralIter};
+
+/// The positions on the path from square `n` back to the centre, moving vertically first and then
+/// horizontally (so the path is a shortest Manhattan path)
+pub fn path_to_centre(n: i64) -> Vec<Position> {
+    let mut position = match index_to_position(n) {
+        Some(position) => position,
+        None           => return vec![],
+    };
+
+    let mut path = vec![position];
+
+    while position.y != 0 {
+        position = if position.y > 0 { position.down() } else { position.up() };
+        path.push(position);
+    }
+
+    while position.x != 0 {
+        position = if position.x > 0 { position.left() } else { position.right() };
+        path.push(position);
+    }
+
+    path
+}
+
+/// Renders the `values` of the squares of the spiral within `radius` rings of the centre
+///
+/// Each column is right aligned to the widest value in that column, and any square on the path
+/// from the square with index `highlight_square` back to the centre is wrapped in `[ ]`.
+///
+/// # Example
+///
+/// The indices for a radius of `1`, highlighting square `9`:
+///
+/// ```text
+///  5   4   3
+///  6  [1] [2]
+///  7   8  [9]
+/// ```
+pub fn render<T: Display>(radius: i64, values: &HashMap<Position, T>, highlight_square: Option<i64>) -> String {
+    let path: HashSet<Position> = highlight_square.map(path_to_centre).unwrap_or_default().into_iter().collect();
+
+    let text: HashMap<&Position, String> = values.iter().map(| (p, v) | (p, v.to_string())).collect();
+
+    let mut widths: HashMap<i64, usize> = HashMap::new();
+    for (position, t) in &text {
+        let width = widths.entry(position.x).or_insert(0);
+        *width = (*width).max(t.len());
+    }
+
+    let mut output = String::new();
+
+    for y in (-radius ..= radius).rev() {
+        let cells: Vec<String> = (-radius ..= radius).map(| x | {
+            let position = Position::new(x, y);
+            let cell = text.get(&position).map(| t | t.as_str()).unwrap_or("");
+            let width = widths.get(&x).cloned().unwrap_or(0);
+
+            if path.contains(&position) {
+                format!("[{:>width$}]", cell, width = width)
+            } else {
+                format!(" {:>width$} ", cell, width = width)
+            }
+        }).collect();
+
+        output.push_str(cells.join(" ").trim_end());
+        output.push('\n');
+    }
+
+    output
+}
+
+/// The number of squares in the spiral within `radius` rings of the centre
+fn squares_within(radius: i64) -> usize {
+    ((2 * radius + 1) * (2 * radius + 1)) as usize
+}
+
+/// Renders the index of each square within `radius` rings of the centre
+pub fn render_indices(radius: i64, highlight_square: Option<i64>) -> String {
+    let values: HashMap<Position, usize> = SpiralIter::default()
+        .take(squares_within(radius))
+        .enumerate()
+        .map(| (i, position) | (position, i + 1))
+        .collect();
+
+    render(radius, &values, highlight_square)
+}
+
+/// Renders the sum of the neighbours of each square within `radius` rings of the centre
+pub fn render_sums(radius: i64, highlight_square: Option<i64>) -> String {
+    let values: HashMap<Position, BigUint> =
+        SpiralFill::new(BigUint::from(1u32), Neighbourhood::Eight, | v: &[BigUint] | v.iter().sum())
+            .take(squares_within(radius))
+            .collect();
+
+    render(radius, &values, highlight_square)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_path_to_centre() {
+        let path: Vec<(i64, i64)> = path_to_centre(22).iter().map(| p | (p.x, p.y)).collect();
+
+        assert_eq!(path, vec![(-1, -2), (-1, -1), (-1, 0), (0, 0)]);
+        assert_eq!(path_to_centre(1), vec![Position::default()]);
+        assert_eq!(path_to_centre(0), vec![]);
+    }
+
+    #[test]
+    fn test_render_indices() {
+        assert_eq!(
+            render_indices(2, None),
+            concat!(
+                " 17   16   15   14   13\n",
+                " 18    5    4    3   12\n",
+                " 19    6    1    2   11\n",
+                " 20    7    8    9   10\n",
+                " 21   22   23   24   25\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_highlighted_path() {
+        assert_eq!(
+            render_indices(1, Some(9)),
+            concat!(
+                " 5   4   3\n",
+                " 6  [1] [2]\n",
+                " 7   8  [9]\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_sums() {
+        assert_eq!(
+            render_sums(2, Some(23)),
+            concat!(
+                " 147   142   133   122    59\n",
+                " 304     5     4     2    57\n",
+                " 330    10  [  1]    1    54\n",
+                " 351    11  [ 23]   25    26\n",
+                " 362   747  [806]  880   931\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_column_widths() {
+        // Each column is only as wide as it's own widest value
+        let values: HashMap<Position, u32> = vec![
+            (Position::new(-1, 1), 1), (Position::new(0, 1), 22), (Position::new(1, 1), 3),
+            (Position::new(-1, 0), 4444), (Position::new(0, 0), 5), (Position::new(1, 0), 6),
+        ].into_iter().collect();
+
+        assert_eq!(
+            render(1, &values, Some(2)),
+            concat!(
+                "    1   22   3\n",
+                " 4444  [ 5] [6]\n",
+                "\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// A canonical form of a word which is the same for every anagram of it
+///
+/// Words made only of `a` to `z` are keyed on a count of each letter, which needs no allocation.
+/// Any other word (or one with a letter repeated more than 255 times) falls back to it's
+/// characters in sorted order.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Signature {
+    LetterCount([u8; 26]),
+    Sorted(Box<[char]>),
+}
+
+impl Signature {
+    pub fn of(word: &str) -> Signature {
+        let mut counts = [0u8; 26];
+
+        for byte in word.bytes() {
+            if !byte.is_ascii_lowercase() {
+                return Signature::sorted(word);
+            }
+
+            let count = &mut counts[(byte - b'a') as usize];
+            match count.checked_add(1) {
+                Some(next) => *count = next,
+                None       => return Signature::sorted(word),
+            }
+        }
+
+        Signature::LetterCount(counts)
+    }
+
+    fn sorted(word: &str) -> Signature {
+        let mut chars: Vec<char> = word.chars().collect();
+        chars.sort_unstable();
+
+        Signature::Sorted(chars.into_boxed_slice())
+    }
+}
+
+/// Groups every word in a corpus by it's `Signature`, so all the anagrams of a word can be found
+/// with a single lookup
+///
+/// The words are stored back to back in one `String`, and each group is a list of offsets into
+/// it, so adding a word allocates nothing more than it's own bytes once it's buffer has grown.
+/// Each distinct word is only stored once.
+#[derive(Debug, Default)]
+pub struct AnagramIndex {
+    text: String,
+    words: Vec<Range<usize>>,
+    groups: HashMap<Signature, Vec<usize>>,
+}
+
+impl AnagramIndex {
+    pub fn new() -> Self { AnagramIndex::default() }
+
+    /// Builds an index of every (whitespace separated) word in the corpus
+    pub fn from_corpus(corpus: &str) -> Self {
+        let mut index = AnagramIndex::new();
+
+        for word in corpus.split_whitespace() {
+            index.insert(word);
+        }
+
+        index
+    }
+
+    /// Adds the word to the index, returning false if it was already there
+    pub fn insert(&mut self, word: &str) -> bool {
+        let (text, words) = (&self.text, &self.words);
+        let group = self.groups.entry(Signature::of(word)).or_default();
+
+        if group.iter().any(| &id | &text[words[id].clone()] == word) {
+            return false;
+        }
+
+        let start = self.text.len();
+        self.text.push_str(word);
+
+        group.push(self.words.len());
+        self.words.push(start .. self.text.len());
+
+        true
+    }
+
+    /// The number of distinct words in the index
+    pub fn len(&self) -> usize { self.words.len() }
+
+    pub fn is_empty(&self) -> bool { self.words.is_empty() }
+
+    fn word(&self, id: usize) -> &str {
+        &self.text[self.words[id].clone()]
+    }
+
+    /// Every word in the index which is an anagram of `word` (not including `word` itself)
+    pub fn anagrams_of<'a>(&'a self, word: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.groups.get(&Signature::of(word))
+            .into_iter()
+            .flat_map(move | group | group.iter().map(move | &id | self.word(id)))
+            .filter(move | other | *other != word)
+    }
+
+    /// Every set of two or more words which are all anagrams of each other
+    pub fn groups(&self) -> impl Iterator<Item = Vec<&str>> {
+        self.groups.values()
+            .filter(| group | group.len() > 1)
+            .map(move | group | group.iter().map(| &id | self.word(id)).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_signature() {
+        assert_eq!(Signature::of("listen"), Signature::of("silent"));
+        assert_ne!(Signature::of("listen"), Signature::of("listens"));
+        assert_eq!(Signature::of("Été"), Signature::of("éÉt"));
+        assert_ne!(Signature::of("abc"), Signature::of("Abc"));
+
+        // Too many of one letter to count in a byte
+        let long: String = "a".repeat(300);
+        assert_eq!(Signature::of(&long), Signature::Sorted(vec!['a'; 300].into_boxed_slice()));
+    }
+
+    #[test]
+    fn test_anagrams_of() {
+        let index = AnagramIndex::from_corpus("listen silent enlist google tinsel banana silent");
+
+        let mut anagrams: Vec<&str> = index.anagrams_of("listen").collect();
+        anagrams.sort();
+
+        assert_eq!(anagrams, vec!["enlist", "silent", "tinsel"]);
+        assert_eq!(index.anagrams_of("inlets").count(), 4);
+        assert_eq!(index.anagrams_of("google").count(), 0);
+        assert_eq!(index.anagrams_of("missing").count(), 0);
+        assert_eq!(index.len(), 6);
+    }
+
+    #[test]
+    fn test_groups() {
+        let index = AnagramIndex::from_corpus("abc cab xyz bca zzz yxz");
+
+        let mut groups: Vec<Vec<&str>> = index.groups().collect();
+        groups.sort();
+
+        assert_eq!(groups, vec![vec!["abc", "cab", "bca"], vec!["xyz", "yxz"]]);
+    }
+
+    #[test]
+    fn test_many_words() {
+        let mut index = AnagramIndex::new();
+
+        // Every arrangement of five letters
+        let letters = ['a', 'b', 'c', 'd', 'e'];
+        for i in 0 .. 5usize.pow(5) {
+            let word: String = (0 .. 5).map(| d | letters[i / 5usize.pow(d) % 5]).collect();
+            index.insert(&word);
+        }
+
+        assert_eq!(index.len(), 3125);
+        assert_eq!(index.anagrams_of("abcde").count(), 119);
+        assert_eq!(index.anagrams_of("aaaaa").count(), 0);
+        assert!(!index.insert("edcba"));
+    }
+}
@@ -0,0 +1,4 @@
+extern crate unicode_normalization;
+
+pub mod anagram;
+pub mod policy;
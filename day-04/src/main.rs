@@ -1,3 +1,7 @@
+extern crate day_04;
+
+use day_04::policy::{Policy, Rule};
+
 #[cfg(not(test))]
 const INPUT: &'static str = include_str!("input.txt");
 
@@ -16,42 +20,12 @@ fn no_of_valid(input: &str, predicate: &Fn(&str) -> bool) -> u32 {
 
 /// Checks if the password has duplicates in it
 fn has_no_duplicates(password: &str) -> bool {
-    use std::collections::HashSet;
-
-    let mut set = HashSet::new();
-
-    for word in password.split_whitespace() {
-        if set.contains(word) {
-            return false;
-        }
-
-        set.insert(word);
-    }
-
-    true
+    Policy::default().rule(Rule::NoDuplicates).check(password).is_empty()
 }
 
 /// Checks if the password has any anagrams
 fn has_no_anagrams(password: &str) -> bool {
-    use std::collections::HashSet;
-    use std::iter::FromIterator;
-
-    let mut set = HashSet::new();
-
-    for word in password.split_whitespace() {
-        let mut chars: Vec<char> = word.chars().collect();
-        chars.sort();
-
-        let string = String::from_iter(chars);
-
-        if set.contains(&string) {
-            return false;
-        }
-
-        set.insert(string);
-    }
-
-    true
+    Policy::default().rule(Rule::NoAnagrams).check(password).is_empty()
 }
 
 #[cfg(test)]
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use anagram::Signature;
+use unicode_normalization::UnicodeNormalization;
+
+/// A class of characters which words may be restricted to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CharacterClass {
+    /// `a` to `z` only
+    AsciiLowercase,
+
+    /// Any Unicode letter
+    Alphabetic,
+
+    /// Any Unicode letter or digit
+    Alphanumeric,
+}
+
+impl CharacterClass {
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            CharacterClass::AsciiLowercase => c.is_ascii_lowercase(),
+            CharacterClass::Alphabetic     => c.is_alphabetic(),
+            CharacterClass::Alphanumeric   => c.is_alphanumeric(),
+        }
+    }
+}
+
+/// A single rule a passphrase must follow
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rule {
+    /// No word may appear more than once
+    NoDuplicates,
+
+    /// No word may be an anagram of another word
+    NoAnagrams,
+
+    /// The passphrase must have at least this many words
+    MinWords(usize),
+
+    /// The passphrase must have at most this many words
+    MaxWords(usize),
+
+    /// Every character of every word must be in this class
+    Characters(CharacterClass),
+}
+
+/// A reason a passphrase broke a rule
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    /// The two words which are the same
+    Duplicate(String, String),
+
+    /// The two words which are anagrams of each other
+    Anagram(String, String),
+
+    /// The passphrase has `count` words, fewer than the `min` allowed
+    TooFewWords { count: usize, min: usize },
+
+    /// The passphrase has `count` words, more than the `max` allowed
+    TooManyWords { count: usize, max: usize },
+
+    /// The first character in the word which isn't in the allowed class
+    InvalidCharacter { word: String, character: char, class: CharacterClass },
+}
+
+/// The result of checking a single passphrase
+#[derive(Debug, PartialEq)]
+pub struct Audit<'a> {
+    /// The line number (starting at 1) of the passphrase
+    pub line: usize,
+    pub passphrase: &'a str,
+    pub violations: Vec<Violation>,
+}
+
+impl<'a> Audit<'a> {
+    pub fn is_valid(&self) -> bool { self.violations.is_empty() }
+}
+
+/// A set of rules passphrases are checked against
+///
+/// # Example
+///
+/// ```text
+/// Policy::default()
+///     .rule(Rule::NoAnagrams)
+///     .rule(Rule::MinWords(3))
+///     .case_insensitive()
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Policy {
+    rules: Vec<Rule>,
+    case_insensitive: bool,
+    normalised: bool,
+}
+
+impl Policy {
+    /// Adds a rule to this policy
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Compare words ignoring their case
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Compare words after Unicode (NFKC) normalisation, so `ﬁ` and `fi` are the same word
+    pub fn normalised(mut self) -> Self {
+        self.normalised = true;
+        self
+    }
+
+    /// The form of the word used when comparing it against other words
+    fn comparable(&self, word: &str) -> String {
+        let word: String = if self.normalised { word.nfkc().collect() } else { String::from(word) };
+
+        if self.case_insensitive { word.to_lowercase() } else { word }
+    }
+
+    /// Finds every pair of words which have the same key, pairing each word with the first word
+    /// that had it's key
+    fn collisions<K, F>(&self, words: &[&str], key: F) -> Vec<(String, String)>
+        where K: Hash + Eq, F: Fn(&str) -> K
+    {
+        let mut seen: HashMap<K, &str> = HashMap::new();
+        let mut collisions = vec![];
+
+        for word in words {
+            let key = key(&self.comparable(word));
+
+            match seen.get(&key) {
+                Some(first) => collisions.push((String::from(*first), String::from(*word))),
+                None        => { seen.insert(key, word); },
+            }
+        }
+
+        collisions
+    }
+
+    /// Checks a single passphrase, returning every rule it breaks
+    pub fn check(&self, passphrase: &str) -> Vec<Violation> {
+        let words: Vec<&str> = passphrase.split_whitespace().collect();
+        let mut violations = vec![];
+
+        for rule in &self.rules {
+            match *rule {
+                Rule::NoDuplicates => violations.extend(
+                    self.collisions(&words, | word | word.to_string())
+                        .into_iter()
+                        .map(| (a, b) | Violation::Duplicate(a, b))
+                ),
+                Rule::NoAnagrams => violations.extend(
+                    self.collisions(&words, Signature::of)
+                        .into_iter()
+                        .map(| (a, b) | Violation::Anagram(a, b))
+                ),
+                Rule::MinWords(min) => if words.len() < min {
+                    violations.push(Violation::TooFewWords { count: words.len(), min });
+                },
+                Rule::MaxWords(max) => if words.len() > max {
+                    violations.push(Violation::TooManyWords { count: words.len(), max });
+                },
+                Rule::Characters(class) => violations.extend(
+                    words.iter().filter_map(| word |
+                        word.chars().find(| c | !class.contains(*c)).map(| character |
+                            Violation::InvalidCharacter { word: String::from(*word), character, class }
+                        )
+                    )
+                ),
+            }
+        }
+
+        violations
+    }
+
+    /// Checks each passphrase in the list (separated on new lines)
+    pub fn audit<'a>(&self, input: &'a str) -> Vec<Audit<'a>> {
+        input.lines()
+            .enumerate()
+            .map(| (i, passphrase) | Audit { line: i + 1, passphrase, violations: self.check(passphrase) })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn duplicate(a: &str, b: &str) -> Violation { Violation::Duplicate(String::from(a), String::from(b)) }
+    fn anagram(a: &str, b: &str) -> Violation { Violation::Anagram(String::from(a), String::from(b)) }
+
+    #[test]
+    fn test_no_duplicates() {
+        let policy = Policy::default().rule(Rule::NoDuplicates);
+
+        assert_eq!(policy.check("aa bb cc dd ee"), vec![]);
+        assert_eq!(policy.check("aa bb cc dd aa"), vec![duplicate("aa", "aa")]);
+        assert_eq!(policy.check("aa bb aa bb aa"), vec![duplicate("aa", "aa"), duplicate("bb", "bb"), duplicate("aa", "aa")]);
+    }
+
+    #[test]
+    fn test_no_anagrams() {
+        let policy = Policy::default().rule(Rule::NoAnagrams);
+
+        assert_eq!(policy.check("abcde fghij"), vec![]);
+        assert_eq!(policy.check("abcde xyz ecdab"), vec![anagram("abcde", "ecdab")]);
+        assert_eq!(policy.check("oiii ioii iioi"), vec![anagram("oiii", "ioii"), anagram("oiii", "iioi")]);
+    }
+
+    #[test]
+    fn test_word_counts() {
+        let policy = Policy::default().rule(Rule::MinWords(2)).rule(Rule::MaxWords(3));
+
+        assert_eq!(policy.check("aa"), vec![Violation::TooFewWords { count: 1, min: 2 }]);
+        assert_eq!(policy.check("aa bb cc"), vec![]);
+        assert_eq!(policy.check("aa bb cc dd"), vec![Violation::TooManyWords { count: 4, max: 3 }]);
+    }
+
+    #[test]
+    fn test_characters() {
+        let policy = Policy::default().rule(Rule::Characters(CharacterClass::AsciiLowercase));
+
+        assert_eq!(policy.check("abc def"), vec![]);
+        assert_eq!(
+            policy.check("abc dEf g1h"),
+            vec![
+                Violation::InvalidCharacter { word: String::from("dEf"), character: 'E', class: CharacterClass::AsciiLowercase },
+                Violation::InvalidCharacter { word: String::from("g1h"), character: '1', class: CharacterClass::AsciiLowercase },
+            ]
+        );
+
+        assert_eq!(
+            Policy::default().rule(Rule::Characters(CharacterClass::Alphabetic)).check("café ß"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_comparison() {
+        let exact = Policy::default().rule(Rule::NoDuplicates);
+        let case_insensitive = exact.clone().case_insensitive();
+        let normalised = exact.clone().normalised();
+
+        assert_eq!(exact.check("Hello hello"), vec![]);
+        assert_eq!(case_insensitive.check("Hello hello"), vec![duplicate("Hello", "hello")]);
+
+        // `é` written as a single code point, and as `e` followed by a combining accent
+        assert_eq!(exact.check("caf\u{e9} cafe\u{301}"), vec![]);
+        assert_eq!(normalised.check("caf\u{e9} cafe\u{301}"), vec![duplicate("caf\u{e9}", "cafe\u{301}")]);
+        assert_eq!(normalised.check("\u{fb01}ne fine"), vec![duplicate("\u{fb01}ne", "fine")]);
+    }
+
+    #[test]
+    fn test_audit() {
+        let policy = Policy::default().rule(Rule::NoDuplicates).rule(Rule::MinWords(2));
+        let audit = policy.audit("aa bb\naa aa\ncc");
+
+        assert!(audit[0].is_valid());
+        assert_eq!(audit[1], Audit { line: 2, passphrase: "aa aa", violations: vec![duplicate("aa", "aa")] });
+        assert_eq!(audit[2].violations, vec![Violation::TooFewWords { count: 1, min: 2 }]);
+    }
+}
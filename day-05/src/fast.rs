@@ -0,0 +1,265 @@
+/// A rule for changing an offset after it has been jumped from
+///
+/// Unlike passing a `&dyn Fn` each modifier is it's own type, so the hot loop in `steps` is
+/// compiled separately for each one and the call is inlined.
+pub trait Modifier {
+    fn modify(&self, offset: i32) -> i32;
+
+    /// Two positive offsets which the modifier turns into each other, if there are any
+    ///
+    /// Once every offset at the start of the maze has become one of these they can never change to
+    /// anything else, and always jump forwards, which lets `steps` skip through them.
+    fn two_cycle(&self) -> Option<(i32, i32)> { None }
+}
+
+/// Part 1, every offset is incremented
+pub struct Increment;
+
+impl Modifier for Increment {
+    #[inline]
+    fn modify(&self, offset: i32) -> i32 { offset + 1 }
+}
+
+/// Part 2, offsets of three or more are decremented, and all others are incremented
+pub struct IncrementOrDecrement;
+
+impl Modifier for IncrementOrDecrement {
+    #[inline]
+    fn modify(&self, offset: i32) -> i32 {
+        if offset >= 3 { offset - 1 } else { offset + 1 }
+    }
+
+    fn two_cycle(&self) -> Option<(i32, i32)> { Some((2, 3)) }
+}
+
+/// The number of offsets in each chunk of the settled prefix
+const CHUNK: usize = 8;
+
+/// What happens when the pointer enters a settled chunk
+#[derive(Clone, Copy, Default)]
+struct Pass {
+    /// The chunk afterwards (one bit per offset, set when it's the second of the pair)
+    bits: u8,
+
+    /// Where the pointer leaves to, relative to the start of the chunk
+    exit: u8,
+
+    steps: u8,
+}
+
+/// Every `Pass` through a chunk, indexed by the entry position and then the chunk's bits
+struct PassTable {
+    pair: (i32, i32),
+    passes: Vec<[Pass; 256]>,
+}
+
+impl PassTable {
+    /// Builds the table for a two cycle, or `None` if the offsets are too large for it
+    fn new((first, second): (i32, i32)) -> Option<Self> {
+        if first < 1 || second < 1 || first as usize > CHUNK || second as usize > CHUNK {
+            return None;
+        }
+
+        let passes = (0 .. CHUNK).map(| entry | {
+            let mut row = [Pass::default(); 256];
+
+            for (bits, pass) in row.iter_mut().enumerate() {
+                let (mut bits, mut ptr, mut steps) = (bits as u8, entry, 0);
+
+                while ptr < CHUNK {
+                    let mask = 1 << ptr;
+                    let offset = if bits & mask == 0 { first } else { second };
+
+                    bits ^= mask;
+                    ptr += offset as usize;
+                    steps += 1;
+                }
+
+                *pass = Pass { bits, exit: ptr as u8, steps };
+            }
+
+            row
+        }).collect();
+
+        Some(PassTable { pair: (first, second), passes })
+    }
+
+    fn contains(&self, offset: i32) -> bool {
+        offset == self.pair.0 || offset == self.pair.1
+    }
+
+    /// Packs a chunk of settled offsets into bits
+    fn pack(&self, offsets: &[i32]) -> u8 {
+        offsets.iter()
+            .enumerate()
+            .filter(| &(_, &offset) | offset == self.pair.1)
+            .fold(0, | bits, (i, _) | bits | 1 << i)
+    }
+}
+
+/// Counts the steps taken to escape the maze, or `None` if it isn't escaped within `budget` steps
+/// (if given)
+///
+/// This gives the same answer as running a `JumpMaze`, but only keeps the offsets and the pointer
+/// so it can't report why or where the maze was left, or tell a maze which never escapes from one
+/// which takes a long time to. A pass through a chunk can take a few steps past the budget.
+///
+/// If the modifier has a `two_cycle`, the run of offsets at the start of the maze which have
+/// settled into it is packed into chunks of bits. A pass through a whole chunk is then a single
+/// lookup, rather than a step for each jump.
+pub fn steps<M: Modifier>(offsets: &[i32], modifier: &M, budget: Option<u64>) -> Option<u64> {
+    let mut offsets = offsets.to_vec();
+    let len = offsets.len() as i64;
+
+    let table = modifier.two_cycle().and_then(PassTable::new);
+
+    // Offsets before `settled` are all in the two cycle, and those in the first `chunks.len()`
+    // chunks are only kept up to date in `chunks`
+    let mut settled = 0;
+    let mut chunks: Vec<u8> = vec![];
+
+    let mut ptr: i64 = 0;
+    let mut steps = 0;
+
+    while ptr >= 0 && ptr < len {
+        if budget.is_some_and(| budget | steps >= budget) {
+            return None;
+        }
+
+        let index = ptr as usize;
+
+        if let Some(ref table) = table {
+            let chunk = index / CHUNK;
+
+            if chunk < chunks.len() {
+                let pass = table.passes[index % CHUNK][chunks[chunk] as usize];
+
+                chunks[chunk] = pass.bits;
+                ptr = (chunk * CHUNK) as i64 + i64::from(pass.exit);
+                steps += u64::from(pass.steps);
+
+                continue;
+            }
+        }
+
+        let offset = offsets[index];
+        offsets[index] = modifier.modify(offset);
+        ptr += i64::from(offset);
+        steps += 1;
+
+        if let Some(ref table) = table {
+            if index == settled {
+                while settled < offsets.len() && table.contains(offsets[settled]) {
+                    settled += 1;
+                }
+
+                while (chunks.len() + 1) * CHUNK <= settled {
+                    let start = chunks.len() * CHUNK;
+                    chunks.push(table.pack(&offsets[start .. start + CHUNK]));
+                }
+            }
+        }
+    }
+
+    Some(steps)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use maze::{Exit, JumpMaze};
+
+    /// A maze shaped like the puzzle inputs, with mostly negative offsets of up to the position
+    fn generate(len: usize, mut seed: u64) -> Vec<i32> {
+        (0 .. len).map(| i | {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            let range = i as u64 + 3;
+
+            ((seed >> 33) % range) as i32 - i as i32
+        }).collect()
+    }
+
+    /// Like `generate`, but offsets only jump back at most `max_back`, so even very long mazes are
+    /// escaped in a reasonable number of steps
+    fn generate_short_jumps(len: usize, max_back: usize, mut seed: u64) -> Vec<i32> {
+        (0 .. len).map(| i | {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            let back = i.min(max_back) as u64;
+
+            ((seed >> 33) % (back + 3)) as i32 - back as i32
+        }).collect()
+    }
+
+    fn reference<M: Modifier>(offsets: &[i32], modifier: &M) -> Option<u64> {
+        let mut maze = JumpMaze::new(offsets.to_vec());
+
+        assert!(maze.run(&| offset | modifier.modify(offset), None).escaped());
+        Some(maze.steps())
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(steps(&[0, 3, 0, 1, -3], &Increment, None), Some(5));
+        assert_eq!(steps(&[0, 3, 0, 1, -3], &IncrementOrDecrement, None), Some(10));
+        assert_eq!(steps(&[], &Increment, None), Some(0));
+        assert_eq!(steps(&[-1], &IncrementOrDecrement, None), Some(1));
+    }
+
+    #[test]
+    fn test_budget() {
+        assert_eq!(steps(&[0, 3, 0, 1, -3], &IncrementOrDecrement, Some(10)), Some(10));
+        assert_eq!(steps(&[0, 3, 0, 1, -3], &IncrementOrDecrement, Some(9)), None);
+        assert_eq!(steps(&[0, 3, 0, 1, -3], &IncrementOrDecrement, Some(0)), None);
+    }
+
+    #[test]
+    fn test_pass_table() {
+        let table = PassTable::new((2, 3)).unwrap();
+
+        // `2 2 2 2 2 2 2 2` from the start jumps along every other offset, turning them into 3s
+        let pass = table.passes[0][0];
+        assert_eq!((pass.bits, pass.exit, pass.steps), (0b0101_0101, 8, 4));
+
+        // `3 3 3 3 3 3 3 3` from the second offset
+        let pass = table.passes[1][0xff];
+        assert_eq!((pass.bits, pass.exit, pass.steps), (0b0110_1101, 10, 3));
+
+        assert!(PassTable::new((0, 1)).is_none());
+        assert!(PassTable::new((2, 9)).is_none());
+    }
+
+    #[test]
+    fn test_matches_jump_maze() {
+        for seed in 0 .. 10 {
+            let offsets = generate(300, seed);
+
+            assert_eq!(steps(&offsets, &Increment, None), reference(&offsets, &Increment), "Seed {}", seed);
+            assert_eq!(
+                steps(&offsets, &IncrementOrDecrement, None),
+                reference(&offsets, &IncrementOrDecrement),
+                "Seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_large_maze() {
+        // Over ten million steps
+        let offsets = generate_short_jumps(1_000_000, 10, 7);
+
+        assert_eq!(steps(&offsets, &IncrementOrDecrement, None), reference(&offsets, &IncrementOrDecrement));
+        assert_eq!(steps(&offsets, &Increment, None), reference(&offsets, &Increment));
+    }
+
+    #[test]
+    fn test_escaping_low() {
+        let offsets = [2, 3, 2, -4, 1, -6];
+
+        let mut maze = JumpMaze::new(offsets.to_vec());
+        let exit = maze.run(&| offset | IncrementOrDecrement.modify(offset), None);
+
+        assert!(matches!(exit, Exit::EscapedLow { .. }));
+        assert_eq!(steps(&offsets, &IncrementOrDecrement, None), Some(maze.steps()));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod fast;
+pub mod maze;
+
+pub use maze::{Exit, JumpMaze};
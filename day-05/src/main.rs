@@ -1,10 +1,24 @@
+extern crate day_05;
+
+use day_05::{Exit, JumpMaze};
+use day_05::fast::{self, Increment, IncrementOrDecrement, Modifier};
+
+/// The steps taken quickly before checking if the maze can be escaped at all
+const FAST_BUDGET: u64 = 100_000_000;
+
 #[cfg(not(test))]
 const INPUT: &'static str = include_str!("input.txt");
 
 #[cfg(not(test))]
 fn main() {
-    println!("Part 1: {}", no_of_jumps(INPUT, &increment));
-    println!("Part 2: {}", no_of_jumps(INPUT, &increment_or_decrement));
+    let parts = [("Part 1", no_of_jumps(INPUT, &Increment)), ("Part 2", no_of_jumps(INPUT, &IncrementOrDecrement))];
+
+    for (part, jumps) in &parts {
+        match *jumps {
+            Ok(steps)    => println!("{}: {}", part, steps),
+            Err(ref err) => eprintln!("{}: {}", part, err),
+        }
+    }
 }
 
 /// Loops over the given input of program offsets, and counts how many steps until it leaves the
@@ -31,42 +45,19 @@ fn main() {
 /// `2  4  0  1 (-3)` - jump all the way to the end; leave a `4` behind.
 /// `2 (4) 0  1  -2`  - go back to where we just were; increment `-3` to `-2`.
 /// `2  5  0  1  -2`  - jump `4` steps forward, escaping the maze.
-fn no_of_jumps(input: &str, modifier: &Fn(i32) -> i32) -> i32 {
-    let mut list: Vec<i32> =
-        input.lines()
-            .map(|i| i.parse().expect("Unable to parse input"))
-            .collect();
-
-    let mut ptr = 0;
-    let mut steps = 0;
-
-    while let Some(offset) = list.get_mut(ptr) {
-        steps += 1;
-        let new_ptr = ptr as i32 + *offset;
-        *offset = modifier(*offset);
+///
+/// If the maze is still being run after `FAST_BUDGET` steps, it's run again with `JumpMaze`, which
+/// is slower but can tell if the maze will never be escaped.
+fn no_of_jumps<M: Modifier>(input: &str, modifier: &M) -> Result<u64, String> {
+    let mut maze: JumpMaze = input.parse().expect("Unable to parse input");
 
-        if new_ptr < 0 {
-            // we've broken out of the maze
-            break;
-        } else {
-            ptr = new_ptr as usize;
-        }
+    if let Some(steps) = fast::steps(maze.offsets(), modifier, Some(FAST_BUDGET)) {
+        return Ok(steps);
     }
 
-    steps
-}
-
-/// Increments the given input by 1
-fn increment(input: i32) -> i32 {
-    input + 1
-}
-
-/// Increments the given input by 1 if < 3, otherwise decrements by 1
-fn increment_or_decrement(input: i32) -> i32 {
-    if input >= 3 {
-        input - 1
-    } else {
-        input + 1
+    match maze.run(&| offset | modifier.modify(offset), None) {
+        Exit::NeverEscapes { cycle } => Err(format!("The maze is never escaped, it loops through offsets {:?}", cycle)),
+        _                            => Ok(maze.steps()),
     }
 }
 
@@ -77,16 +68,30 @@ mod test {
     #[test]
     fn part1_example() {
         assert_eq!(
-            no_of_jumps("0\n3\n0\n1\n-3", &increment),
-            5
+            no_of_jumps("0\n3\n0\n1\n-3", &Increment),
+            Ok(5)
         )
     }
 
     #[test]
     fn part2_example() {
         assert_eq!(
-            no_of_jumps("0\n3\n0\n1\n-3", &increment_or_decrement),
-            10
+            no_of_jumps("0\n3\n0\n1\n-3", &IncrementOrDecrement),
+            Ok(10)
+        )
+    }
+
+    struct Unchanged;
+
+    impl Modifier for Unchanged {
+        fn modify(&self, offset: i32) -> i32 { offset }
+    }
+
+    #[test]
+    fn never_escapes() {
+        assert_eq!(
+            no_of_jumps("1\n2\n-1\n-2", &Unchanged),
+            Err(String::from("The maze is never escaped, it loops through offsets [1, 3]"))
         )
     }
 }
@@ -0,0 +1,225 @@
+use std::str::FromStr;
+
+/// Why a run through the maze stopped
+#[derive(Clone, Debug, PartialEq)]
+pub enum Exit {
+    /// Jumped to before the first offset, landing on `to`
+    EscapedLow { to: i64 },
+
+    /// Jumped to past the last offset, landing on `to`
+    EscapedHigh { to: i64 },
+
+    /// The step budget ran out while still inside the maze
+    BudgetExceeded,
+
+    /// The pointer is stuck jumping around this cycle of offsets forever, as each of them is left
+    /// unchanged by the modifier. This is found once the pointer has been round the cycle once.
+    NeverEscapes { cycle: Vec<usize> },
+}
+
+impl Exit {
+    pub fn escaped(&self) -> bool {
+        matches!(*self, Exit::EscapedLow { .. } | Exit::EscapedHigh { .. })
+    }
+}
+
+/// A list of jump offsets being executed one step at a time
+///
+/// Each step jumps from the current offset by it's value, then replaces the offset it left with
+/// the result of the `modifier`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JumpMaze {
+    offsets: Vec<i32>,
+    ptr: usize,
+    steps: u64,
+    visits: Vec<u64>,
+
+    /// The step each offset was last jumped from, or 0 if it never has been
+    last_jumped: Vec<u64>,
+
+    /// The first step of the current run of jumps from offsets the modifier leaves unchanged
+    fixed_since: u64,
+
+    exit: Option<Exit>,
+}
+
+impl FromStr for JumpMaze {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let offsets = input.lines()
+            .enumerate()
+            .filter(| &(_, line) | !line.trim().is_empty())
+            .map(| (i, line) |
+                line.trim().parse().map_err(| _ | format!("Line {}: invalid offset '{}'", i + 1, line.trim()))
+            )
+            .collect::<Result<Vec<i32>, String>>()?;
+
+        Ok(JumpMaze::new(offsets))
+    }
+}
+
+impl JumpMaze {
+    pub fn new(offsets: Vec<i32>) -> Self {
+        let visits = vec![0; offsets.len()];
+
+        // An empty maze has been escaped before it starts
+        let exit = if offsets.is_empty() { Some(Exit::EscapedHigh { to: 0 }) } else { None };
+
+        JumpMaze { last_jumped: visits.clone(), offsets, ptr: 0, steps: 0, visits, fixed_since: 1, exit }
+    }
+
+    /// The offsets as they currently are
+    pub fn offsets(&self) -> &[i32] { &self.offsets }
+
+    /// The index of the offset which will be jumped from next
+    pub fn ptr(&self) -> usize { self.ptr }
+
+    /// The number of steps taken so far
+    pub fn steps(&self) -> u64 { self.steps }
+
+    /// How many times each offset has been jumped from
+    pub fn visits(&self) -> &[u64] { &self.visits }
+
+    /// Takes a single step, returning the reason the maze has stopped if it has
+    ///
+    /// Once the maze has been escaped (or shown to never escape) no more steps are taken, and the
+    /// same reason is returned again.
+    pub fn step(&mut self, modifier: &dyn Fn(i32) -> i32) -> Option<Exit> {
+        if self.exit.is_some() {
+            return self.exit.clone();
+        }
+
+        let offset = self.offsets[self.ptr];
+        let modified = modifier(offset);
+
+        if modified == offset {
+            // None of the offsets jumped from since `fixed_since` can change, so coming back to
+            // one of them means the pointer will keep following the same jumps forever
+            if self.last_jumped[self.ptr] >= self.fixed_since {
+                self.exit = Some(Exit::NeverEscapes { cycle: self.cycle_from(self.ptr) });
+                return self.exit.clone();
+            }
+        } else {
+            self.fixed_since = self.steps + 2;
+        }
+
+        self.steps += 1;
+        self.visits[self.ptr] += 1;
+        self.last_jumped[self.ptr] = self.steps;
+        self.offsets[self.ptr] = modified;
+
+        let to = self.ptr as i64 + i64::from(offset);
+
+        if to < 0 {
+            self.exit = Some(Exit::EscapedLow { to });
+        } else if to >= self.offsets.len() as i64 {
+            self.exit = Some(Exit::EscapedHigh { to });
+        } else {
+            self.ptr = to as usize;
+        }
+
+        self.exit.clone()
+    }
+
+    /// Runs until the maze is escaped, shown to never escape, or `budget` more steps have been
+    /// taken (if given)
+    pub fn run(&mut self, modifier: &dyn Fn(i32) -> i32, budget: Option<u64>) -> Exit {
+        let mut taken = 0;
+
+        loop {
+            if budget.is_some_and(| budget | taken >= budget) {
+                return Exit::BudgetExceeded;
+            }
+
+            if let Some(exit) = self.step(modifier) {
+                return exit;
+            }
+
+            taken += 1;
+        }
+    }
+
+    /// The offsets visited following the jumps from `start` until they return to it
+    fn cycle_from(&self, start: usize) -> Vec<usize> {
+        let mut cycle = vec![start];
+        let mut ptr = (start as i64 + i64::from(self.offsets[start])) as usize;
+
+        while ptr != start {
+            cycle.push(ptr);
+            ptr = (ptr as i64 + i64::from(self.offsets[ptr])) as usize;
+        }
+
+        cycle
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn maze(input: &str) -> JumpMaze { input.parse().unwrap() }
+    fn increment(x: i32) -> i32 { x + 1 }
+    fn unchanged(x: i32) -> i32 { x }
+
+    #[test]
+    fn test_escapes() {
+        let mut example = maze("0\n3\n0\n1\n-3");
+
+        assert_eq!(example.run(&increment, None), Exit::EscapedHigh { to: 5 });
+        assert_eq!(example.steps(), 5);
+        assert_eq!(example.visits(), &[2, 2, 0, 0, 1]);
+        assert_eq!(example.offsets(), &[2, 5, 0, 1, -2]);
+
+        let mut low = maze("1\n-2");
+        assert_eq!(low.run(&increment, None), Exit::EscapedLow { to: -1 });
+        assert_eq!(low.steps(), 2);
+        assert!(low.run(&increment, None).escaped());
+        assert_eq!(low.steps(), 2);
+    }
+
+    #[test]
+    fn test_budget() {
+        let mut example = maze("0\n3\n0\n1\n-3");
+
+        assert_eq!(example.run(&increment, Some(3)), Exit::BudgetExceeded);
+        assert_eq!(example.steps(), 3);
+        assert_eq!(example.ptr(), 4);
+
+        assert_eq!(example.run(&increment, Some(10)), Exit::EscapedHigh { to: 5 });
+        assert_eq!(example.steps(), 5);
+    }
+
+    #[test]
+    fn test_never_escapes() {
+        assert_eq!(maze("0").run(&unchanged, None), Exit::NeverEscapes { cycle: vec![0] });
+        assert_eq!(maze("1\n2\n-1\n-2").run(&unchanged, None), Exit::NeverEscapes { cycle: vec![1, 3] });
+        assert_eq!(maze("1\n2\n0\n1").run(&unchanged, None), Exit::EscapedHigh { to: 4 });
+
+        // Only stuck once the offset reaches a value the modifier leaves alone
+        let mut capped = maze("1\n-1");
+        let exit = capped.run(&| x | if x < 0 { x + 1 } else { x }, Some(100));
+
+        assert_eq!(exit, Exit::NeverEscapes { cycle: vec![1] });
+        assert_eq!(capped.steps(), 4);
+    }
+
+    #[test]
+    fn test_long_fixed_run() {
+        // A long path through offsets which never change, ending in a small loop
+        let mut offsets = vec![1; 100_000];
+        offsets.extend_from_slice(&[1, -1]);
+
+        let mut long = JumpMaze::new(offsets);
+
+        assert_eq!(long.run(&unchanged, None), Exit::NeverEscapes { cycle: vec![100_000, 100_001] });
+        assert_eq!(long.steps(), 100_002);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(maze("1\n\n-2\n").offsets(), &[1, -2]);
+        assert_eq!("1\nx".parse::<JumpMaze>(), Err(String::from("Line 2: invalid offset 'x'")));
+        assert_eq!(maze("").run(&increment, None), Exit::EscapedHigh { to: 0 });
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Which bank is redistributed when several hold the most blocks
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TieBreak {
+    #[default]
+    LowestIndex,
+    HighestIndex,
+}
+
+/// Which way around the banks the blocks are handed out
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Direction {
+    /// Starting with the bank after the one being redistributed
+    #[default]
+    Forwards,
+
+    /// Starting with the bank before the one being redistributed
+    Backwards,
+}
+
+/// What happened when reallocating until a state was seen twice
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// The first state to be seen a second time
+    pub repeated: Vec<u32>,
+
+    /// The number of redistributions before `repeated` was first seen
+    pub first_seen: usize,
+
+    /// The number of redistributions between the two times `repeated` was seen
+    pub cycle_length: usize,
+
+    /// Every state in the loop, starting with `repeated`
+    pub cycle: Vec<Vec<u32>>,
+}
+
+impl Report {
+    /// The number of redistributions before a state was seen for the second time
+    pub fn steps(&self) -> usize {
+        self.first_seen + self.cycle_length
+    }
+}
+
+/// The blocks held in each memory bank, and the rules for reallocating them
+///
+/// The defaults match the puzzle: ties go to the lowest index, and blocks are handed out forwards.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryBanks {
+    banks: Vec<u32>,
+    tie_break: TieBreak,
+    direction: Direction,
+}
+
+impl FromStr for MemoryBanks {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let banks = input.split_whitespace()
+            .map(| num | num.parse().map_err(| _ | format!("Invalid number of blocks '{}'", num)))
+            .collect::<Result<Vec<u32>, String>>()?;
+
+        Ok(MemoryBanks::new(banks))
+    }
+}
+
+impl MemoryBanks {
+    pub fn new(banks: Vec<u32>) -> Self {
+        MemoryBanks { banks, ..MemoryBanks::default() }
+    }
+
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// The blocks in each bank
+    pub fn banks(&self) -> &[u32] { &self.banks }
+
+    /// The index of the bank to redistribute next, or `None` if there are no banks
+    fn fullest(&self) -> Option<usize> {
+        let max = *self.banks.iter().max()?;
+        let mut indices = self.banks.iter().enumerate().filter(| &(_, &blocks) | blocks == max);
+
+        match self.tie_break {
+            TieBreak::LowestIndex  => indices.next(),
+            TieBreak::HighestIndex => indices.next_back(),
+        }.map(| (i, _) | i)
+    }
+
+    /// Empties the fullest bank, and shares its blocks out as if they were handed out one at a time
+    /// to the following banks (or the preceding banks, when going `Backwards`)
+    ///
+    /// Rather than a block at a time, every bank gets an equal share of the blocks at once, and
+    /// the remainder go to the run of banks next to the one emptied in the direction of travel, so
+    /// this doesn't depend on the number of blocks.
+    pub fn redistribute(&mut self) {
+        let index = match self.fullest() {
+            Some(index) => index,
+            None        => return,
+        };
+
+        let len = self.banks.len();
+        let blocks = self.banks[index] as usize;
+        self.banks[index] = 0;
+
+        let (share, remainder) = ((blocks / len) as u32, blocks % len);
+
+        if share > 0 {
+            self.banks.iter_mut().for_each(| bank | *bank += share);
+        }
+
+        let start = match self.direction {
+            Direction::Forwards  => (index + 1) % len,
+            Direction::Backwards => (index + len - remainder) % len,
+        };
+
+        let end = start + remainder;
+        if end <= len {
+            self.banks[start .. end].iter_mut().for_each(| bank | *bank += 1);
+        } else {
+            self.banks[start ..].iter_mut().for_each(| bank | *bank += 1);
+            self.banks[.. end - len].iter_mut().for_each(| bank | *bank += 1);
+        }
+    }
+
+    /// Keeps redistributing until a state is seen for the second time
+    ///
+    /// Only the encoded states are kept while searching, and the states in the loop are found
+    /// afterwards by going around it again.
+    pub fn reallocate(&mut self) -> Report {
+        let mut seen: HashMap<State, usize> = HashMap::new();
+        let mut steps = 0;
+
+        let (first_seen, cycle_length) = loop {
+            let state = State::encode(&self.banks);
+
+            if let Some(&first_seen) = seen.get(&state) {
+                break (first_seen, steps - first_seen);
+            }
+
+            seen.insert(state, steps);
+            steps += 1;
+
+            self.redistribute();
+        };
+
+        let mut replay = self.clone();
+        let cycle = (0 .. cycle_length).map(| _ | {
+            let state = replay.banks.clone();
+            replay.redistribute();
+            state
+        }).collect();
+
+        Report { repeated: self.banks.clone(), first_seen, cycle_length, cycle }
+    }
+}
+
+/// A compact encoding of the blocks in each bank, for remembering which states have been seen
+///
+/// Each bank is written as a variable length integer, seven bits to a byte, so the small numbers
+/// most banks hold only take up a single byte.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct State(Box<[u8]>);
+
+impl State {
+    pub fn encode(banks: &[u32]) -> Self {
+        let mut bytes = Vec::with_capacity(banks.len());
+
+        for &bank in banks {
+            let mut value = bank;
+
+            while value >= 0x80 {
+                bytes.push((value & 0x7f) as u8 | 0x80);
+                value >>= 7;
+            }
+
+            bytes.push(value as u8);
+        }
+
+        State(bytes.into_boxed_slice())
+    }
+
+    pub fn decode(&self) -> Vec<u32> {
+        let mut banks = vec![];
+        let (mut value, mut shift) = (0, 0);
+
+        for &byte in self.0.iter() {
+            value |= u32::from(byte & 0x7f) << shift;
+            shift += 7;
+
+            if byte & 0x80 == 0 {
+                banks.push(value);
+                value = 0;
+                shift = 0;
+            }
+        }
+
+        banks
+    }
+
+    /// The number of bytes used to encode the state
+    pub fn len(&self) -> usize { self.0.len() }
+
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn banks(input: &str) -> MemoryBanks { input.parse().unwrap() }
+
+    #[test]
+    fn test_example() {
+        let report = banks("0 2 7 0").reallocate();
+
+        assert_eq!(report.steps(), 5);
+        assert_eq!(report.first_seen, 1);
+        assert_eq!(report.cycle_length, 4);
+        assert_eq!(report.repeated, vec![2, 4, 1, 2]);
+        assert_eq!(
+            report.cycle,
+            vec![vec![2, 4, 1, 2], vec![3, 1, 2, 3], vec![0, 2, 3, 4], vec![1, 3, 4, 1]]
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let mut highest = banks("3 0 3").tie_break(TieBreak::HighestIndex);
+        highest.redistribute();
+        assert_eq!(highest.banks(), &[4, 1, 1]);
+
+        let mut forwards = banks("1 0 5 0");
+        forwards.redistribute();
+        assert_eq!(forwards.banks(), &[2, 1, 1, 2]);
+
+        let mut backwards = banks("1 0 5 0").direction(Direction::Backwards);
+        backwards.redistribute();
+        assert_eq!(backwards.banks(), &[2, 2, 1, 1]);
+
+        let report = banks("0 2 7 0").direction(Direction::Backwards).tie_break(TieBreak::HighestIndex).reallocate();
+        assert_eq!(report.cycle.len(), report.cycle_length);
+        assert_eq!(report.cycle[0], report.repeated);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(banks("").reallocate().steps(), 1);
+        assert_eq!(banks("5").reallocate(), Report { repeated: vec![5], first_seen: 0, cycle_length: 1, cycle: vec![vec![5]] });
+        assert!("1 x".parse::<MemoryBanks>().is_err());
+    }
+
+    /// Hands out the blocks one at a time, as the puzzle describes
+    fn redistribute_slowly(banks: &mut MemoryBanks) {
+        let index = match banks.fullest() {
+            Some(index) => index,
+            None        => return,
+        };
+
+        let len = banks.banks.len();
+        let blocks = banks.banks[index];
+        banks.banks[index] = 0;
+
+        let mut i = index;
+        for _ in 0 .. blocks {
+            i = match banks.direction {
+                Direction::Forwards  => (i + 1) % len,
+                Direction::Backwards => (i + len - 1) % len,
+            };
+
+            banks.banks[i] += 1;
+        }
+    }
+
+    #[test]
+    fn test_matches_redistributing_slowly() {
+        for &direction in &[Direction::Forwards, Direction::Backwards] {
+            for &input in &["0 2 7 0", "14 0 15 12 11 11 3 5 1 6 8 4 9 1 8 4", "3 1", "100 0 0", "0"] {
+                let mut fast = banks(input).direction(direction);
+                let mut slow = fast.clone();
+
+                for _ in 0 .. 100 {
+                    fast.redistribute();
+                    redistribute_slowly(&mut slow);
+
+                    assert_eq!(fast.banks(), slow.banks(), "{} {:?}", input, direction);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_huge_banks() {
+        let mut huge = MemoryBanks::new(vec![4_000_000_000, 7, 0, 12, 3]);
+        huge.redistribute();
+        assert_eq!(huge.banks(), &[800_000_000, 800_000_007, 800_000_000, 800_000_012, 800_000_003]);
+
+        let report = MemoryBanks::new(vec![1_000_000_000, 0, 0, 5, 0, 0, 3]).reallocate();
+        assert_eq!(report.cycle.len(), report.cycle_length);
+    }
+
+    #[test]
+    fn test_state_encoding() {
+        let banks = vec![0, 1, 127, 128, 300, 1_000_000, u32::MAX];
+        let state = State::encode(&banks);
+
+        assert_eq!(state.decode(), banks);
+        assert_eq!(state.len(), 1 + 1 + 1 + 2 + 2 + 3 + 5);
+        assert_eq!(State::encode(&[0, 2, 7, 0]).len(), 4);
+        assert_ne!(State::encode(&[1, 0]), State::encode(&[1]));
+    }
+}
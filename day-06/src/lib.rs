@@ -0,0 +1,3 @@
+pub mod banks;
+
+pub use banks::{Direction, MemoryBanks, Report, State, TieBreak};
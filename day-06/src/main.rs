@@ -1,46 +1,26 @@
+extern crate day_06;
+
+use day_06::MemoryBanks;
+
 #[cfg(not(test))]
-const INPUT: &'static str = include_str!("input.txt");
+const INPUT: &str = include_str!("input.txt");
 
 #[cfg(not(test))]
 fn main() {
     println!("Part 1: {}", how_many_cycles(INPUT, false));
-    println!("Part 1: {}", how_many_cycles(INPUT, true));
+    println!("Part 2: {}", how_many_cycles(INPUT, true));
 }
 
-fn how_many_cycles(input: &str, return_cycle_size: bool) -> u32 {
-    use std::collections::HashMap;
-
-    let mut banks: Vec<u32> = input.split_whitespace()
-        .map(|num| num.parse().expect("Invalid"))
-        .collect();
-
-    let mut states_seen = HashMap::new();
-
-
-    let mut counter = 0;
-    let len = banks.len();
-
-    while states_seen.contains_key(&banks) == false {
-        states_seen.insert(banks.clone(), counter);
-
-        counter = counter + 1;
-
-        if let Some((max_index, &max_value)) =
-            banks.iter().enumerate().rev()
-            .max_by_key(|&(_, val)| val) {
-
-            banks[max_index] = 0;
-
-            (0..len).cycle()
-                .skip(max_index + 1).take(max_value as usize)
-                .for_each(|i| banks[i] += 1);
-        }
-    }
+/// Counts the redistributions until a state is repeated, or the length of the loop that state is
+/// in if `return_cycle_size` is set
+fn how_many_cycles(input: &str, return_cycle_size: bool) -> usize {
+    let mut banks: MemoryBanks = input.parse().expect("Invalid");
+    let report = banks.reallocate();
 
     if return_cycle_size {
-        counter - states_seen.get(&banks).unwrap()
+        report.cycle_length
     } else {
-        counter
+        report.steps()
     }
 }
 
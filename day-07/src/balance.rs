@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use tower::{NodeId, Tower};
+
+/// A child whose tree weight is different to most of it's siblings
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outlier {
+    pub name: String,
+
+    /// The tree weight of most of it's siblings
+    pub expected: u64,
+
+    pub actual: u64,
+}
+
+/// What is wrong with the children of an unbalanced program
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    /// Most of the children have the same tree weight, apart from these
+    Outliers(Vec<Outlier>),
+
+    /// No tree weight is held by more of the children than any other, so there is no way to tell
+    /// which are wrong (such as two children of different weights). Holds the tree weight of each
+    /// child.
+    Ambiguous(Vec<(String, u64)>),
+}
+
+/// A program whose children don't all have the same tree weight
+#[derive(Clone, Debug, PartialEq)]
+pub struct Imbalance {
+    pub name: String,
+    pub verdict: Verdict,
+}
+
+/// A suggested change to the weight of a single program
+#[derive(Clone, Debug, PartialEq)]
+pub struct Correction {
+    pub name: String,
+    pub from: u32,
+    pub to: u32,
+}
+
+/// Every imbalance in a tower, and the corrections which would fix them
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Analysis {
+    /// Ordered from the bottom of the tower upwards
+    pub imbalances: Vec<Imbalance>,
+
+    /// Ordered from the top of the tower downwards, as the corrections higher up assume the
+    /// ones above them have been made
+    pub corrections: Vec<Correction>,
+}
+
+impl Analysis {
+    pub fn is_balanced(&self) -> bool { self.imbalances.is_empty() }
+}
+
+/// The tree weight held by more of the `weights` than any other, if there is one
+fn majority(weights: &[u64]) -> Option<u64> {
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+
+    for &weight in weights {
+        *counts.entry(weight).or_insert(0) += 1;
+    }
+
+    let most = *counts.values().max()?;
+    let mut candidates = counts.into_iter().filter(| &(_, count) | count == most);
+
+    match (candidates.next(), candidates.next()) {
+        (Some((weight, _)), None) => Some(weight),
+        _                         => None,
+    }
+}
+
+/// Finds every program in the tower whose children aren't balanced
+///
+/// The corrections are worked out from the top of the tower down: once the programs above have
+/// been corrected, any child which still doesn't match most of it's siblings has it's own weight
+/// changed to make up the difference. So if correcting a program higher up also fixes the
+/// imbalance below it, nothing more is changed. No corrections are suggested for children of an
+/// ambiguous imbalance.
+pub fn analyse(tower: &Tower) -> Analysis {
+    let mut analysis = Analysis::default();
+
+    // The tree weight of each node once all corrections above it have been made
+    let mut corrected: HashMap<NodeId, u64> = tower.ids().map(| id | (id, tower[id].tree_weight)).collect();
+
+    // As each program comes before those it holds, going backwards works from the top down
+    for id in tower.ids().rev() {
+        let node = &tower[id];
+        let children = tower.children(id);
+
+        if children.is_empty() {
+            continue;
+        }
+
+        if !tower.children_balanced(id) {
+            let actual: Vec<u64> = children.iter().map(| &child | tower[child].tree_weight).collect();
+
+            let verdict = match majority(&actual) {
+                Some(expected) => Verdict::Outliers(
+                    children.iter()
+                        .map(| &child | &tower[child])
+                        .filter(| child | child.tree_weight != expected)
+                        .map(| child | Outlier { name: child.name.clone(), expected, actual: child.tree_weight })
+                        .collect()
+                ),
+                None => Verdict::Ambiguous(
+                    children.iter().map(| &child | (tower[child].name.clone(), tower[child].tree_weight)).collect()
+                ),
+            };
+
+            analysis.imbalances.push(Imbalance { name: node.name.clone(), verdict });
+        }
+
+        let weights: Vec<u64> = children.iter().map(| child | corrected[child]).collect();
+
+        if let Some(expected) = majority(&weights) {
+            for &c in children {
+                let child = &tower[c];
+                let change = expected as i64 - corrected[&c] as i64;
+                let to = i64::from(child.self_weight) + change;
+
+                // A weight has to fit in a `u32`, so this child can't be fixed on it's own
+                if change == 0 || to < 0 || to > i64::from(u32::MAX) {
+                    continue;
+                }
+
+                analysis.corrections.push(Correction { name: child.name.clone(), from: child.self_weight, to: to as u32 });
+                corrected.insert(c, expected);
+            }
+        }
+
+        let total = u64::from(node.self_weight) + children.iter().map(| child | corrected[child]).sum::<u64>();
+        corrected.insert(id, total);
+    }
+
+    analysis.imbalances.reverse();
+
+    analysis
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tower::read_tower;
+
+    fn outlier(name: &str, expected: u64, actual: u64) -> Outlier {
+        Outlier { name: String::from(name), expected, actual }
+    }
+
+    fn correction(name: &str, from: u32, to: u32) -> Correction {
+        Correction { name: String::from(name), from, to }
+    }
+
+    #[test]
+    fn test_example() {
+        let tower = read_tower("
+            pbga (66)
+            xhth (57)
+            ebii (61)
+            havc (66)
+            ktlj (57)
+            fwft (72) -> ktlj, cntj, xhth
+            qoyq (66)
+            padx (45) -> pbga, havc, qoyq
+            tknk (41) -> ugml, padx, fwft
+            jptl (61)
+            ugml (68) -> gyxo, ebii, jptl
+            gyxo (61)
+            cntj (57)
+        ").unwrap();
+
+        let analysis = analyse(&tower);
+
+        assert_eq!(
+            analysis.imbalances,
+            vec![Imbalance { name: String::from("tknk"), verdict: Verdict::Outliers(vec![outlier("ugml", 243, 251)]) }]
+        );
+        assert_eq!(analysis.corrections, vec![correction("ugml", 68, 60)]);
+    }
+
+    #[test]
+    fn test_balanced() {
+        let analysis = analyse(&read_tower("a (1) -> b, c\nb (2)\nc (2)").unwrap());
+
+        assert!(analysis.is_balanced());
+        assert_eq!(analysis.corrections, vec![]);
+    }
+
+    #[test]
+    fn test_several_imbalances() {
+        let tower = read_tower("
+            root (1) -> a, b, c
+            a (2) -> d, e, f
+            b (5) -> g, h, i
+            c (11)
+            d (1)
+            e (1)
+            f (2)
+            g (2)
+            h (2)
+            i (4)
+        ").unwrap();
+
+        let analysis = analyse(&tower);
+
+        // No two of `a`, `b` and `c` agree, so it's ambiguous which is wrong until `b` has been
+        // corrected to match `c`
+        assert_eq!(
+            analysis.imbalances,
+            vec![
+                Imbalance {
+                    name: String::from("root"),
+                    verdict: Verdict::Ambiguous(vec![(String::from("a"), 6), (String::from("b"), 13), (String::from("c"), 11)]),
+                },
+                Imbalance { name: String::from("a"), verdict: Verdict::Outliers(vec![outlier("f", 1, 2)]) },
+                Imbalance { name: String::from("b"), verdict: Verdict::Outliers(vec![outlier("i", 2, 4)]) },
+            ]
+        );
+        assert_eq!(analysis.corrections, vec![correction("i", 4, 2), correction("f", 2, 1), correction("a", 2, 8)]);
+    }
+
+    #[test]
+    fn test_nested_corrections() {
+        // Fixing `f` also balances the root
+        let fixed_below = read_tower("root (1) -> a, b, c\na (1) -> d, e, f\nb (4)\nc (4)\nd (1)\ne (1)\nf (2)").unwrap();
+        let analysis = analyse(&fixed_below);
+
+        assert_eq!(analysis.imbalances.len(), 2);
+        assert_eq!(analysis.corrections, vec![correction("f", 2, 1)]);
+
+        // Fixing `f` isn't enough on it's own, so `a` has to change too
+        let fixed_both = read_tower("root (1) -> a, b, c\na (2) -> d, e, f\nb (4)\nc (4)\nd (1)\ne (1)\nf (3)").unwrap();
+        let analysis = analyse(&fixed_both);
+
+        assert_eq!(analysis.corrections, vec![correction("f", 3, 1), correction("a", 2, 1)]);
+    }
+
+    #[test]
+    fn test_ambiguous() {
+        let analysis = analyse(&read_tower("a (1) -> b, c\nb (2)\nc (3)").unwrap());
+
+        assert_eq!(
+            analysis.imbalances,
+            vec![Imbalance { name: String::from("a"), verdict: Verdict::Ambiguous(vec![(String::from("b"), 2), (String::from("c"), 3)]) }]
+        );
+        assert_eq!(analysis.corrections, vec![]);
+    }
+}
@@ -0,0 +1,32 @@
+extern crate day_07;
+extern crate utils;
+
+use std::io;
+use std::process;
+
+use day_07::export::{write_dot, write_json};
+use day_07::tower::read_tower;
+
+/// Reads a tower and writes it out as either Graphviz DOT (the default) or JSON
+fn main() {
+    let json = match std::env::args().nth(1).as_deref() {
+        None | Some("--dot") => false,
+        Some("--json")       => true,
+        Some(other)          => { eprintln!("Unknown format `{}`, expected --dot or --json", other); process::exit(1); },
+    };
+
+    let tower = match read_tower(&utils::read_all_input()) {
+        Ok(tower) => tower,
+        Err(err)  => { eprintln!("Unable to read the tower: {}", err); process::exit(1); },
+    };
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    let result = if json { write_json(&tower, &mut out) } else { write_dot(&tower, &mut out) };
+
+    if let Err(err) = result {
+        eprintln!("Unable to write the tower: {}", err);
+        process::exit(1);
+    }
+}
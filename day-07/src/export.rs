@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+use std::io;
+use std::io::Write;
+
+use balance::{analyse, Verdict};
+use tower::{NodeId, Tower};
+
+/// Writes `text` as a quoted string, escaping it for both DOT and JSON
+fn write_quoted<W: Write>(out: &mut W, text: &str) -> io::Result<()> {
+    write!(out, "\"")?;
+
+    for c in text.chars() {
+        match c {
+            '"'  => write!(out, "\\\"")?,
+            '\\' => write!(out, "\\\\")?,
+            '\n' => write!(out, "\\n")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c    => write!(out, "{}", c)?,
+        }
+    }
+
+    write!(out, "\"")
+}
+
+/// Writes the tower as a Graphviz graph
+///
+/// Each program is labelled with it's name, and it's own weight along with the weight of it's
+/// subtree. Programs whose children aren't balanced are outlined in red, and the subtrees which
+/// are the wrong weight are filled in red.
+pub fn write_dot<W: Write>(tower: &Tower, out: &mut W) -> io::Result<()> {
+    let analysis = analyse(tower);
+
+    let outliers: HashSet<&str> = analysis.imbalances.iter()
+        .flat_map(| imbalance | match imbalance.verdict {
+            Verdict::Outliers(ref outliers) => outliers.iter().map(| o | o.name.as_str()).collect(),
+            Verdict::Ambiguous(_)           => vec![],
+        })
+        .collect();
+
+    writeln!(out, "digraph tower {{")?;
+    writeln!(out, "    node [shape=box];")?;
+
+    for id in tower.ids() {
+        let node = &tower[id];
+
+        write!(out, "    {} [label=", node_name(id))?;
+        write_quoted(out, &format!("{}\n{} / {}", node.name, node.self_weight, node.tree_weight))?;
+
+        if !tower.children_balanced(id) {
+            write!(out, ", color=red, penwidth=2")?;
+        }
+
+        if outliers.contains(node.name.as_str()) {
+            write!(out, ", style=filled, fillcolor=\"#f4cccc\"")?;
+        }
+
+        writeln!(out, "];")?;
+    }
+
+    for id in tower.ids() {
+        for &child in tower.children(id) {
+            writeln!(out, "    {} -> {};", node_name(id), node_name(child))?;
+        }
+    }
+
+    writeln!(out, "}}")
+}
+
+fn node_name(id: NodeId) -> String {
+    format!("n{}", id.index())
+}
+
+/// Writes the tower as nested JSON objects on a single line
+///
+/// Each program is written as
+///
+/// ```text
+/// {"name": "...", "weight": 1, "tree_weight": 2, "balanced": true, "children": [...]}
+/// ```
+///
+/// with the programs it holds in `children`.
+pub fn write_json<W: Write>(tower: &Tower, out: &mut W) -> io::Result<()> {
+    let open = | out: &mut W, id: NodeId | -> io::Result<()> {
+        let node = &tower[id];
+
+        write!(out, "{{\"name\": ")?;
+        write_quoted(out, &node.name)?;
+        write!(
+            out,
+            ", \"weight\": {}, \"tree_weight\": {}, \"balanced\": {}, \"children\": [",
+            node.self_weight,
+            node.tree_weight,
+            tower.children_balanced(id)
+        )
+    };
+
+    // Each program being written, along with how many of it's children have been written
+    let mut stack = vec![(tower.root(), 0)];
+    open(out, tower.root())?;
+
+    while let Some(&mut (id, ref mut written)) = stack.last_mut() {
+        match tower.children(id).get(*written) {
+            Some(&child) => {
+                if *written > 0 {
+                    write!(out, ", ")?;
+                }
+
+                *written += 1;
+                open(out, child)?;
+                stack.push((child, 0));
+            },
+            None => {
+                write!(out, "]}}")?;
+                stack.pop();
+            },
+        }
+    }
+
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tower::read_tower;
+
+    fn dot(tower: &Tower) -> String {
+        let mut out = vec![];
+        write_dot(tower, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn json(tower: &Tower) -> String {
+        let mut out = vec![];
+        write_json(tower, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_dot() {
+        let tower = read_tower("a (1) -> b, c, d\nb (2)\nc (2)\nd (3) -> e\ne (1)").unwrap();
+
+        assert_eq!(
+            dot(&tower),
+            concat!(
+                "digraph tower {\n",
+                "    node [shape=box];\n",
+                "    n0 [label=\"a\\n1 / 9\", color=red, penwidth=2];\n",
+                "    n1 [label=\"b\\n2 / 2\"];\n",
+                "    n2 [label=\"c\\n2 / 2\"];\n",
+                "    n3 [label=\"d\\n3 / 4\", style=filled, fillcolor=\"#f4cccc\"];\n",
+                "    n4 [label=\"e\\n1 / 1\"];\n",
+                "    n0 -> n1;\n",
+                "    n0 -> n2;\n",
+                "    n0 -> n3;\n",
+                "    n3 -> n4;\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let tower = read_tower("a (1) -> b, c\nb (2) -> d\nc (3)\nd (1)").unwrap();
+
+        assert_eq!(
+            json(&tower),
+            concat!(
+                "{\"name\": \"a\", \"weight\": 1, \"tree_weight\": 7, \"balanced\": true, \"children\": [",
+                "{\"name\": \"b\", \"weight\": 2, \"tree_weight\": 3, \"balanced\": true, \"children\": [",
+                "{\"name\": \"d\", \"weight\": 1, \"tree_weight\": 1, \"balanced\": true, \"children\": []}",
+                "]}, ",
+                "{\"name\": \"c\", \"weight\": 3, \"tree_weight\": 3, \"balanced\": true, \"children\": []}",
+                "]}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_quoting() {
+        let mut out = vec![];
+        write_quoted(&mut out, "a \"b\" \\ c\n\t").unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "\"a \\\"b\\\" \\\\ c\\n\\u0009\"");
+    }
+}
@@ -0,0 +1,5 @@
+extern crate regex;
+
+pub mod balance;
+pub mod export;
+pub mod tower;
@@ -1,17 +1,23 @@
-extern crate regex;
+extern crate day_07;
 
-#[cfg(not(test))]
-const INPUT: &'static str = include_str!("input.txt");
+use day_07::tower;
 
-mod tower;
+#[cfg(not(test))]
+const INPUT: &str = include_str!("input.txt");
 
 #[cfg(not(test))]
 fn main() {
-    let bottom = tower::read_tower(INPUT);
+    let tower = match tower::read_tower(INPUT) {
+        Ok(tower) => tower,
+        Err(err)   => {
+            eprintln!("Unable to read the tower: {}", err);
+            std::process::exit(1);
+        },
+    };
 
-    println!("Part 1: {}", bottom.name);
+    println!("Part 1: {}", tower[tower.root()].name);
 
-    let change = bottom.required_change_to_balance();
+    let change = tower.required_change_to_balance();
     println!("Part 2: {:?}", change);
 }
 
@@ -21,7 +27,7 @@ mod test {
 
     #[test]
     fn test_read_tower() {
-        let bottom = tower::read_tower("
+        let tower = tower::read_tower("
             pbga (66)
             xhth (57)
             ebii (61)
@@ -35,12 +41,12 @@ mod test {
             ugml (68) -> gyxo, ebii, jptl
             gyxo (61)
             cntj (57)
-        ");
+        ").unwrap();
 
-        assert_eq!(bottom.name, "tknk");
+        assert_eq!(tower[tower.root()].name, "tknk");
 
         println!("Part 2");
-        bottom.print();
-        assert_eq!(bottom.required_change_to_balance(), Some(60));
+        tower.print();
+        assert_eq!(tower.required_change_to_balance(), Some(60));
     }
 }
@@ -1,141 +1,667 @@
+use balance::analyse;
 use regex::Regex;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::ops::Index;
+
+/// The index of a program in it's `Tower`
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// The position of the program in the tower, counting breadth first from the bottom
+    pub fn index(self) -> usize { self.0 }
+}
 
 #[derive(Clone, Debug)]
 pub struct Node {
     pub name: String,
     pub self_weight: u32,
-    pub tree_weight: u32,
-    children: Vec<Node>,
+    pub tree_weight: u64,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
 }
 
 impl Node {
-    pub fn print(&self) {
-        self.print_tree(0);
+    /// The program holding this one, or `None` for the bottom program
+    pub fn parent(&self) -> Option<NodeId> { self.parent }
+
+    pub fn children(&self) -> &[NodeId] { &self.children }
+
+    pub fn is_leaf(&self) -> bool { self.children.is_empty() }
+}
+
+/// A tower of programs, stored in a single list and linked by their `NodeId`
+///
+/// The programs are stored in breadth first order from the bottom of the tower, so each program
+/// comes before all of the programs it holds, and the programs it holds are next to each other.
+/// None of the queries recurse, so very tall towers can't overflow the stack.
+#[derive(Clone, Debug)]
+pub struct Tower {
+    nodes: Vec<Node>,
+    names: HashMap<String, NodeId>,
+
+    /// Every program whose children aren't balanced, kept up to date as weights are changed
+    unbalanced: BTreeSet<NodeId>,
+}
+
+/// The programs whose balance changed after a weight was set
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BalanceChange {
+    pub now_balanced: Vec<NodeId>,
+    pub now_unbalanced: Vec<NodeId>,
+}
+
+impl Index<NodeId> for Tower {
+    type Output = Node;
+
+    fn index(&self, id: NodeId) -> &Node { &self.nodes[id.0] }
+}
+
+impl Tower {
+    /// The program at the bottom of the tower
+    pub fn root(&self) -> NodeId { NodeId(0) }
+
+    /// The number of programs in the tower
+    pub fn len(&self) -> usize { self.nodes.len() }
+
+    /// Always false, as a tower can't be read without any programs
+    pub fn is_empty(&self) -> bool { self.nodes.is_empty() }
+
+    /// Every program, with each coming before all the programs it holds
+    pub fn ids(&self) -> impl DoubleEndedIterator<Item = NodeId> {
+        (0 .. self.nodes.len()).map(NodeId)
     }
 
-    fn print_tree(&self, tab: u32) {
-        if tab == 0 {
-            println!("{} ({} / {})", self.name, self.self_weight, self.tree_weight);
-        } else {
-            let prefix = (1..tab).map(|_| "|  ").collect::<String>();
-            println!("{}|- {} ({} / {})", prefix, self.name, self.self_weight, self.tree_weight);
+    /// Looks up a program by it's name
+    pub fn find(&self, name: &str) -> Option<NodeId> {
+        self.names.get(name).cloned()
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self[id].parent
+    }
+
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self[id].children
+    }
+
+    /// The programs from the bottom of the tower up to and including `id`
+    pub fn path_from_root(&self, id: NodeId) -> Vec<NodeId> {
+        let mut path = vec![id];
+        let mut current = id;
+
+        while let Some(parent) = self.parent(current) {
+            path.push(parent);
+            current = parent;
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// How many programs are below `id`, so the bottom program is at depth 0
+    pub fn depth(&self, id: NodeId) -> usize {
+        let mut depth = 0;
+        let mut current = id;
+
+        while let Some(parent) = self.parent(current) {
+            depth += 1;
+            current = parent;
+        }
+
+        depth
+    }
+
+    /// The weight of `id` and every program it's holding
+    pub fn subtree_weight(&self, id: NodeId) -> u64 {
+        self[id].tree_weight
+    }
+
+    /// The highest program which has both `a` and `b` in it's subtree (which may be `a` or `b`)
+    pub fn lowest_common_ancestor(&self, a: NodeId, b: NodeId) -> NodeId {
+        let (mut a, mut b) = (a, b);
+        let (mut depth_a, mut depth_b) = (self.depth(a), self.depth(b));
+
+        while depth_a > depth_b {
+            a = self.parent(a).expect("Only the root has no parent");
+            depth_a -= 1;
+        }
+
+        while depth_b > depth_a {
+            b = self.parent(b).expect("Only the root has no parent");
+            depth_b -= 1;
         }
 
-        let next_tab = tab + 1;
+        while a != b {
+            a = self.parent(a).expect("Only the root has no parent");
+            b = self.parent(b).expect("Only the root has no parent");
+        }
+
+        a
+    }
 
-        for child in &self.children {
-            child.print_tree(next_tab);
+    /// Every program which isn't holding any others
+    pub fn leaves(&self) -> Vec<NodeId> {
+        self.ids().filter(| &id | self[id].is_leaf()).collect()
+    }
+
+    pub fn print(&self) {
+        let mut stack = vec![(self.root(), 0)];
+
+        while let Some((id, tab)) = stack.pop() {
+            let node = &self[id];
+
+            if tab == 0 {
+                println!("{} ({} / {})", node.name, node.self_weight, node.tree_weight);
+            } else {
+                let prefix = (1..tab).map(|_| "|  ").collect::<String>();
+                println!("{}|- {} ({} / {})", prefix, node.name, node.self_weight, node.tree_weight);
+            }
+
+            stack.extend(node.children.iter().rev().map(| &child | (child, tab + 1)));
         }
     }
 
     /// Are the children of this node balanced?
-    pub fn children_balanced(&self) -> bool {
-        match self.children.iter().next() {
+    pub fn children_balanced(&self, id: NodeId) -> bool {
+        match self[id].children.first() {
             None =>
                 true,
-            Some(first_child) =>
-                self.children.iter()
-                    .all( | child | child.tree_weight == first_child.tree_weight )
+            Some(&first_child) =>
+                self[id].children.iter()
+                    .all( | &child | self[child].tree_weight == self[first_child].tree_weight )
         }
     }
 
+    /// Every program whose children aren't balanced
+    pub fn unbalanced(&self) -> &BTreeSet<NodeId> { &self.unbalanced }
 
-    pub fn required_change_to_balance(&self) -> Option<u32> {
-        // If we and our children are balanced, nothing to check!
-        if self.children_balanced() {
-            return None
+    pub fn is_balanced(&self) -> bool { self.unbalanced.is_empty() }
+
+    /// Changes the weight of a single program
+    ///
+    /// Only the tree weights of the programs below it change, so only those programs can become
+    /// balanced or unbalanced, and nothing else in the tower is looked at.
+    pub fn set_weight(&mut self, id: NodeId, weight: u32) -> BalanceChange {
+        let old = self[id].self_weight;
+        self.nodes[id.0].self_weight = weight;
+
+        // Each tree weight includes the old weight, so taking it away can't go below zero
+        let mut current = Some(id);
+        while let Some(node) = current {
+            let tree_weight = &mut self.nodes[node.0].tree_weight;
+            *tree_weight = *tree_weight - u64::from(old) + u64::from(weight);
+
+            current = self.parent(node);
         }
 
-        let mut sub_weights = HashMap::new();
+        let mut balance = BalanceChange::default();
 
-        // Depth first check of children
-        for child in &self.children {
-            let result = child.required_change_to_balance();
+        let mut current = self.parent(id);
+        while let Some(node) = current {
+            let balanced = self.children_balanced(node);
 
-            if result.is_some() {
-                return result;
+            if balanced && self.unbalanced.remove(&node) {
+                balance.now_balanced.push(node);
+            } else if !balanced && self.unbalanced.insert(node) {
+                balance.now_unbalanced.push(node);
             }
 
-            let mut list = sub_weights.remove(&child.tree_weight).unwrap_or(vec![]);
-            list.push(child);
-            sub_weights.insert(child.tree_weight, list);
+            current = self.parent(node);
         }
 
-        if sub_weights.len() > 1 {
-            let (max_weight, _) = sub_weights.iter().max_by_key( | i| ((*i).1.len(), i.0) ).unwrap();
-            let (min_weight, min_nodes) = sub_weights.iter().min_by_key( | i| ((*i).1.len(), i.0) ).unwrap();
+        balance
+    }
+
+    /// The new weight for the first program which needs correcting to balance the tower, see
+    /// `balance::analyse` for every correction
+    pub fn required_change_to_balance(&self) -> Option<u32> {
+        analyse(self).corrections.first().map(| correction | correction.to)
+    }
+}
+
+/// The reasons a tower dump can not be read
+#[derive(Clone, Debug, PartialEq)]
+pub enum TowerError {
+    /// A line (numbered from 1) which isn't of the form `name (weight) -> child, ...`
+    UnparsableLine { line: usize, text: String },
+
+    /// A program listed on two lines
+    DuplicateProgram { name: String, first_line: usize, line: usize },
 
-            assert_eq!(min_nodes.len(), 1, "Expected only one node to be off!");
+    /// A program listed as holding a program which isn't in the dump
+    UnknownChild { parent: String, child: String, line: usize },
 
-            let child: &Node = min_nodes.iter().next().unwrap();
-            let change = *max_weight as i32 - *min_weight as i32;
+    /// A program listed more than once as being held by the same program
+    DuplicateChild { parent: String, child: String, line: usize },
 
-            Some((child.self_weight as i32 + change) as u32)
-        } else {
-            panic!("We should have more than one weight!");
+    /// A program held by more than one other program
+    MultipleParents { child: String, parents: Vec<String> },
+
+    /// Programs which (indirectly) hold themselves, in the order they hold each other
+    Cycle(Vec<String>),
+
+    /// More than one program isn't held by anything
+    MultipleRoots(Vec<String>),
+
+    /// There were no programs in the dump
+    Empty,
+}
+
+impl fmt::Display for TowerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TowerError::UnparsableLine { line, ref text } =>
+                write!(f, "Line {}: unable to parse `{}`", line, text),
+            TowerError::DuplicateProgram { ref name, first_line, line } =>
+                write!(f, "Line {}: `{}` was already listed on line {}", line, name, first_line),
+            TowerError::UnknownChild { ref parent, ref child, line } =>
+                write!(f, "Line {}: `{}` holds `{}`, which isn't listed", line, parent, child),
+            TowerError::DuplicateChild { ref parent, ref child, line } =>
+                write!(f, "Line {}: `{}` holds `{}` more than once", line, parent, child),
+            TowerError::MultipleParents { ref child, ref parents } =>
+                write!(f, "`{}` is held by more than one program: {}", child, parents.join(", ")),
+            TowerError::Cycle(ref names) =>
+                write!(f, "Programs hold each other in a loop: {} -> {}", names.join(" -> "), names[0]),
+            TowerError::MultipleRoots(ref names) =>
+                write!(f, "More than one program is at the bottom: {}", names.join(", ")),
+            TowerError::Empty =>
+                write!(f, "There are no programs in the tower"),
         }
     }
 }
 
-pub fn read_tower(input: &str) -> Node {
+impl Error for TowerError {}
+
+/// A program as listed in the dump
+struct Listing {
+    line: usize,
+    weight: u32,
+    children: Vec<String>,
+}
+
+/// Reads the tower from a list of programs, the weight of each, and the programs they hold
+pub fn read_tower(input: &str) -> Result<Tower, TowerError> {
     let re = Regex::new(
-        r"^\s*([a-z]+) \((\d+)\)(\s+->\s+([a-z, ]+))?$"
+        r"^([a-z]+) \((\d+)\)(\s+->\s+([a-z, ]+))?$"
     ).expect("Unable to compile regex");
 
-    let mut str_nodes    = HashMap::new();
-    let mut node_weights = HashMap::new();
-    let mut possible_parents = HashSet::new();
-    let mut is_child = HashSet::new();
+    // Read all the data into a map, keeping the order the programs were listed in
+    let mut listings: HashMap<String, Listing> = HashMap::new();
+    let mut order = vec![];
+
+    for (i, line) in input.lines().enumerate() {
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let unparsable = || TowerError::UnparsableLine { line: i + 1, text: String::from(text) };
+
+        let cap = re.captures(text).ok_or_else(unparsable)?;
+        let name = String::from(&cap[1]);
+        let weight = cap[2].parse().map_err(| _ | unparsable())?;
+
+        let children = match cap.get(4) {
+            Some(links) => links.as_str()
+                .split(',')
+                .map(| child | String::from(child.trim()))
+                .collect::<Vec<String>>(),
+            None => vec![],
+        };
+
+        if children.iter().any(| child | child.is_empty()) {
+            return Err(unparsable());
+        }
+
+        if let Some(first) = listings.get(&name) {
+            return Err(TowerError::DuplicateProgram { name, first_line: first.line, line: i + 1 });
+        }
+
+        order.push(name.clone());
+        listings.insert(name, Listing { line: i + 1, weight, children });
+    }
+
+    // Check every child exists, and has a single parent
+    let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
 
-    // Read all the dat into maps
-    input.trim().lines().for_each(|line| {
-        for cap in re.captures_iter(line) {
-            let name   = String::from(&cap[1]);
-            let weight = cap[2].parse().expect("Unable to parse weight");
+    for name in &order {
+        let listing = &listings[name];
 
-            if is_child.contains(&name) == false {
-                possible_parents.insert(name.clone());
+        for child in &listing.children {
+            if !listings.contains_key(child) {
+                return Err(TowerError::UnknownChild {
+                    parent: name.clone(),
+                    child: child.clone(),
+                    line: listing.line,
+                });
             }
 
-            // Does it have links
-            let children = match cap.get(4) {
-                Some(links) => {
-                    let children: Vec<&str> = links.as_str().split(", ").collect();
-                    let children_str: Vec<String> = children.iter().map(|child| String::from(*child)).collect();
+            let holders = parents.entry(child).or_default();
 
-                    children_str.iter().for_each( | child | {
-                        is_child.insert(child.clone());
-                        possible_parents.remove(&child.clone());
-                    });
+            if holders.last() == Some(&name.as_str()) {
+                return Err(TowerError::DuplicateChild {
+                    parent: name.clone(),
+                    child: child.clone(),
+                    line: listing.line,
+                });
+            }
 
-                    children_str
-                },
-                None => vec![],
-            };
+            holders.push(name);
+        }
+    }
 
-            str_nodes.insert(name.clone(), children);
-            node_weights.insert(name.clone(), weight);
+    for name in &order {
+        if let Some(holders) = parents.get(name.as_str()) {
+            if holders.len() > 1 {
+                return Err(TowerError::MultipleParents {
+                    child: name.clone(),
+                    parents: holders.iter().map(| parent | String::from(*parent)).collect(),
+                });
+            }
         }
-    });
+    }
+
+    // As every program has at most one parent, following the parents from any program either
+    // reaches the bottom of the tower or goes round in a loop
+    let mut finished: HashSet<&str> = HashSet::new();
 
-    // Convert the maps into a tree of nodes
-    assert_eq!(possible_parents.len(), 1, "Expected only 1 parent");
-    let root_name = possible_parents.iter().next().unwrap();
+    for name in &order {
+        let mut path: Vec<&str> = vec![];
+        let mut on_path: HashMap<&str, usize> = HashMap::new();
+        let mut current: &str = name;
 
-    fn build_tree(node_name: &String, node_weights: &HashMap<String, u32>, str_nodes: &HashMap<String, Vec<String>>) -> Node {
-        let weight = *node_weights.get(node_name).unwrap();
-        let children = str_nodes.get(node_name).unwrap();
-        let children: Vec<Node> = children.iter().map( | child_name | build_tree(child_name, node_weights, str_nodes) ).collect();
+        while !finished.contains(current) {
+            if let Some(&start) = on_path.get(current) {
+                let mut cycle: Vec<String> = path[start ..].iter().rev().map(| n | String::from(*n)).collect();
+                cycle.rotate_right(1);
 
-        Node {
-            name: node_name.clone(),
-            self_weight: weight,
-            tree_weight: children.iter().fold(weight, | sum, n | sum + n.tree_weight),
-            children,
+                return Err(TowerError::Cycle(cycle));
+            }
+
+            on_path.insert(current, path.len());
+            path.push(current);
+
+            match parents.get(current) {
+                Some(holders) => current = holders[0],
+                None          => break,
+            }
         }
+
+        finished.extend(path);
+    }
+
+    let roots: Vec<&String> = order.iter().filter(| name | !parents.contains_key(name.as_str())).collect();
+
+    let root_name = match roots.len() {
+        0 => return Err(TowerError::Empty),
+        1 => roots[0],
+        _ => return Err(TowerError::MultipleRoots(roots.into_iter().cloned().collect())),
     };
 
-    build_tree(root_name, &node_weights, &str_nodes)
+    // Lay out the programs breadth first from the bottom, then add up the tree weights from the
+    // top down
+    let mut tower = Tower { nodes: vec![], names: HashMap::new(), unbalanced: BTreeSet::new() };
+    let mut queue = VecDeque::new();
+    queue.push_back((root_name, None));
+
+    while let Some((name, parent)) = queue.pop_front() {
+        let id = NodeId(tower.nodes.len());
+        let listing = &listings[name];
+
+        tower.nodes.push(Node {
+            name: name.clone(),
+            self_weight: listing.weight,
+            tree_weight: u64::from(listing.weight),
+            parent,
+            children: vec![],
+        });
+        tower.names.insert(name.clone(), id);
+
+        if let Some(parent) = parent {
+            tower.nodes[parent.0].children.push(id);
+        }
+
+        queue.extend(listing.children.iter().map(| child | (child, Some(id))));
+    }
+
+    for i in (1 .. tower.nodes.len()).rev() {
+        let (weight, parent) = (tower.nodes[i].tree_weight, tower.nodes[i].parent.expect("Only the root has no parent"));
+        tower.nodes[parent.0].tree_weight += weight;
+    }
+
+    tower.unbalanced = tower.ids().filter(| &id | !tower.children_balanced(id)).collect();
+
+    Ok(tower)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn error(input: &str) -> TowerError {
+        read_tower(input).unwrap_err()
+    }
+
+    #[test]
+    fn test_valid_tower() {
+        let tower = read_tower("a (1) -> b, c\nb (2)\n\nc (3) -> d\nd (4)").unwrap();
+
+        assert_eq!(tower[tower.root()].name, "a");
+        assert_eq!(tower[tower.root()].tree_weight, 10);
+    }
+
+    #[test]
+    fn test_unparsable_lines() {
+        assert_eq!(
+            error("a (1) -> b\n\nb 2"),
+            TowerError::UnparsableLine { line: 3, text: String::from("b 2") }
+        );
+        assert_eq!(
+            error("a (99999999999)"),
+            TowerError::UnparsableLine { line: 1, text: String::from("a (99999999999)") }
+        );
+        assert_eq!(
+            error("a (1) -> b,,c\nb (1)\nc (1)"),
+            TowerError::UnparsableLine { line: 1, text: String::from("a (1) -> b,,c") }
+        );
+    }
+
+    #[test]
+    fn test_bad_references() {
+        assert_eq!(
+            error("a (1) -> b\nb (2)\na (3)"),
+            TowerError::DuplicateProgram { name: String::from("a"), first_line: 1, line: 3 }
+        );
+        assert_eq!(
+            error("a (1) -> b\nb (2) -> c"),
+            TowerError::UnknownChild { parent: String::from("b"), child: String::from("c"), line: 2 }
+        );
+        assert_eq!(
+            error("a (1) -> c\nb (1) -> c\nc (1)"),
+            TowerError::MultipleParents { child: String::from("c"), parents: vec![String::from("a"), String::from("b")] }
+        );
+        assert_eq!(
+            error("a (1) -> b, c\nb (1)\nc (1) -> d, e, d\nd (1)\ne (1)"),
+            TowerError::DuplicateChild { parent: String::from("c"), child: String::from("d"), line: 3 }
+        );
+    }
+
+    #[test]
+    fn test_roots() {
+        assert_eq!(error(""), TowerError::Empty);
+        assert_eq!(
+            error("a (1)\nb (1) -> c\nc (1)"),
+            TowerError::MultipleRoots(vec![String::from("a"), String::from("b")])
+        );
+    }
+
+    #[test]
+    fn test_cycles() {
+        assert_eq!(
+            error("root (1) -> x\nx (1)\na (1) -> b\nb (1) -> c\nc (1) -> a"),
+            TowerError::Cycle(vec![String::from("a"), String::from("b"), String::from("c")])
+        );
+        assert_eq!(error("a (1) -> a"), TowerError::Cycle(vec![String::from("a")]));
+        assert_eq!(
+            error("a (1)\nb (1) -> c\nc (1) -> b").to_string(),
+            "Programs hold each other in a loop: b -> c -> b"
+        );
+    }
+
+    fn example() -> Tower {
+        read_tower(example_text()).unwrap()
+    }
+
+    fn example_text() -> &'static str {
+        "
+            pbga (66)
+            xhth (57)
+            ebii (61)
+            havc (66)
+            ktlj (57)
+            fwft (72) -> ktlj, cntj, xhth
+            qoyq (66)
+            padx (45) -> pbga, havc, qoyq
+            tknk (41) -> ugml, padx, fwft
+            jptl (61)
+            ugml (68) -> gyxo, ebii, jptl
+            gyxo (61)
+            cntj (57)
+        "
+    }
+
+    #[test]
+    fn test_queries() {
+        let tower = example();
+        let id = | name | tower.find(name).unwrap();
+        let names = | ids: Vec<NodeId> | ids.iter().map(| &id | tower[id].name.clone()).collect::<Vec<String>>();
+
+        assert_eq!(tower.len(), 13);
+        assert_eq!(tower.find("nope"), None);
+        assert_eq!(tower.parent(id("ebii")), Some(id("ugml")));
+        assert_eq!(tower.parent(tower.root()), None);
+        assert_eq!(names(tower.children(id("padx")).to_vec()), vec!["pbga", "havc", "qoyq"]);
+
+        assert_eq!(names(tower.path_from_root(id("cntj"))), vec!["tknk", "fwft", "cntj"]);
+        assert_eq!(tower.depth(id("cntj")), 2);
+        assert_eq!(tower.depth(tower.root()), 0);
+        assert_eq!(tower.subtree_weight(id("ugml")), 251);
+
+        assert_eq!(tower.lowest_common_ancestor(id("ktlj"), id("xhth")), id("fwft"));
+        assert_eq!(tower.lowest_common_ancestor(id("ktlj"), id("havc")), id("tknk"));
+        assert_eq!(tower.lowest_common_ancestor(id("ugml"), id("jptl")), id("ugml"));
+
+        let mut leaves = names(tower.leaves());
+        leaves.sort();
+        assert_eq!(leaves, vec!["cntj", "ebii", "gyxo", "havc", "jptl", "ktlj", "pbga", "qoyq", "xhth"]);
+    }
+
+    #[test]
+    fn test_breadth_first_order() {
+        let tower = example();
+
+        for id in tower.ids() {
+            let children = tower.children(id);
+
+            assert!(children.iter().all(| &child | child > id));
+            assert!(children.windows(2).all(| pair | pair[1] == NodeId(pair[0].0 + 1)));
+        }
+    }
+
+    #[test]
+    fn test_tall_tower() {
+        let height = 100_000;
+
+        // Listed from the top down, so following the parents during validation is the longest walk
+        let input: String = (0 .. height).rev()
+            .map(| i | if i + 1 == height {
+                format!("p{} (1)\n", to_letters(i))
+            } else {
+                format!("p{} (1) -> p{}\n", to_letters(i), to_letters(i + 1))
+            })
+            .collect();
+
+        let tower = read_tower(&input).unwrap();
+        let top = tower.find(&format!("p{}", to_letters(height - 1))).unwrap();
+
+        assert_eq!(tower[tower.root()].tree_weight, height as u64);
+        assert_eq!(tower.depth(top), height - 1);
+        assert_eq!(tower.path_from_root(top).len(), height);
+        assert_eq!(tower.lowest_common_ancestor(top, tower.root()), tower.root());
+        assert_eq!(tower.leaves(), vec![top]);
+        assert_eq!(tower.required_change_to_balance(), None);
+    }
+
+    #[test]
+    fn test_set_weight() {
+        let mut tower = example();
+        let id = | tower: &Tower, name | tower.find(name).unwrap();
+        let (tknk, ugml, padx, pbga) = (id(&tower, "tknk"), id(&tower, "ugml"), id(&tower, "padx"), id(&tower, "pbga"));
+
+        assert_eq!(tower.unbalanced().iter().cloned().collect::<Vec<NodeId>>(), vec![tknk]);
+
+        // Break `padx` as well, which leaves `tknk` still unbalanced
+        assert_eq!(tower.set_weight(pbga, 70), BalanceChange { now_balanced: vec![], now_unbalanced: vec![padx] });
+        assert_eq!(tower.subtree_weight(padx), 247);
+        assert_eq!(tower.subtree_weight(tknk), 782);
+
+        // Fixing `ugml` isn't enough now `padx` is too heavy
+        assert_eq!(tower.set_weight(ugml, 60), BalanceChange::default());
+        assert_eq!(tower.unbalanced().len(), 2);
+
+        assert_eq!(tower.set_weight(pbga, 66), BalanceChange { now_balanced: vec![padx, tknk], now_unbalanced: vec![] });
+        assert!(tower.is_balanced());
+        assert_eq!(tower.subtree_weight(tknk), 770);
+        assert_eq!(tower.required_change_to_balance(), None);
+    }
+
+    #[test]
+    fn test_heavy_towers() {
+        let mut tower = read_tower("a (4294967295) -> b, c, d\nb (1)\nc (1)\nd (1)").unwrap();
+        assert_eq!(tower.subtree_weight(tower.root()), 4_294_967_298);
+
+        let b = tower.find("b").unwrap();
+        assert_eq!(tower.set_weight(b, u32::MAX), BalanceChange { now_balanced: vec![], now_unbalanced: vec![tower.root()] });
+        assert_eq!(tower.subtree_weight(tower.root()), 8_589_934_592);
+        assert_eq!(tower.required_change_to_balance(), Some(1));
+    }
+
+    #[test]
+    fn test_set_weight_matches_rereading() {
+        let mut tower = example();
+        let ebii = tower.find("ebii").unwrap();
+        tower.set_weight(ebii, 5);
+
+        let reread = read_tower(&example_text().replace("ebii (61)", "ebii (5)")).unwrap();
+
+        for id in tower.ids() {
+            let node = &tower[id];
+            let other = &reread[reread.find(&node.name).unwrap()];
+
+            assert_eq!((node.self_weight, node.tree_weight), (other.self_weight, other.tree_weight), "{}", node.name);
+            assert_eq!(tower.unbalanced().contains(&id), reread.unbalanced().contains(&reread.find(&node.name).unwrap()));
+        }
+    }
+
+    /// Program names can only be made of letters
+    fn to_letters(mut i: usize) -> String {
+        let mut name = vec![];
+
+        loop {
+            name.push(b'a' + (i % 26) as u8);
+            i /= 26;
+
+            if i == 0 {
+                break;
+            }
+        }
+
+        String::from_utf8(name).unwrap()
+    }
 }
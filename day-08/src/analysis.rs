@@ -0,0 +1,308 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use comparison::{Comparision, ComparisionOperator, Condition};
+use instruction::{Action, Instruction, Operation};
+use operand::Operand;
+
+/// The smallest and largest values a register could hold
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    pub min: i32,
+    pub max: i32,
+}
+
+/// What can be worked out about a program without running it
+#[derive(Debug, PartialEq)]
+pub struct Analysis {
+    /// Registers used in conditions or as values, but never changed
+    pub only_read: BTreeSet<String>,
+
+    /// Registers which are changed, but never used in conditions or as values
+    pub only_written: BTreeSet<String>,
+
+    /// Conditions which always, or never, hold, by instruction index
+    pub constant_conditions: BTreeMap<usize, bool>,
+
+    /// Every value each register could hold, including the starting 0
+    pub ranges: BTreeMap<String, Interval>,
+}
+
+type State = BTreeMap<String, Interval>;
+
+/// Passes over the program before bounds which are still growing are widened to the limits of an `i32`
+const WIDEN_AFTER: usize = 3;
+
+impl Interval {
+    pub fn exactly(value: i32) -> Self { Interval { min: value, max: value } }
+
+    pub fn contains(self, value: i32) -> bool { self.min <= value && value <= self.max }
+
+    /// The smallest interval holding both
+    pub fn join(self, other: Interval) -> Self {
+        Interval { min: self.min.min(other.min), max: self.max.max(other.max) }
+    }
+
+    /// The interval spanning the given values, clamped to those an `i32` can hold
+    fn spanning(values: &[i64]) -> Self {
+        let clamp = | value: i64 | value.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+
+        Interval {
+            min: clamp(*values.iter().min().unwrap()),
+            max: clamp(*values.iter().max().unwrap()),
+        }
+    }
+
+    fn constant(self) -> Option<i32> {
+        if self.min == self.max { Some(self.min) } else { None }
+    }
+
+    /// The values `op` could give when applied to a value from `self` with one from `value`
+    ///
+    /// Overflows stop the program, so results are clamped to the limits of an `i32`.
+    fn apply(self, op: Operation, value: Interval) -> Self {
+        let (a, b) = ((i64::from(self.min), i64::from(self.max)), (i64::from(value.min), i64::from(value.max)));
+
+        // Dividing by only zero always fails, so the register can only keep it's value
+        let nonzero_divisor = value != Interval::exactly(0);
+
+        match op {
+            Operation::INC => Interval::spanning(&[a.0 + b.0, a.1 + b.1]),
+            Operation::DEC => Interval::spanning(&[a.0 - b.1, a.1 - b.0]),
+            Operation::MUL => Interval::spanning(&[a.0 * b.0, a.0 * b.1, a.1 * b.0, a.1 * b.1]),
+            Operation::SET => value,
+            Operation::DIV if nonzero_divisor => {
+                // Division is monotonic either side of zero, so only the corners of each side matter
+                let mut results = vec![];
+
+                for &(low, high) in &[(b.0, b.1.min(-1)), (b.0.max(1), b.1)] {
+                    if low <= high {
+                        results.extend_from_slice(&[a.0 / low, a.0 / high, a.1 / low, a.1 / high]);
+                    }
+                }
+
+                Interval::spanning(&results)
+            },
+            Operation::MOD if nonzero_divisor => {
+                // The result takes the sign of the register, and is smaller than the largest divisor
+                let largest = b.0.abs().max(b.1.abs()) - 1;
+
+                Interval::spanning(&[a.0.max(-largest).min(0), a.1.min(largest).max(0)])
+            },
+            Operation::DIV | Operation::MOD => self,
+        }
+    }
+}
+
+fn operand(state: &State, operand: &Operand) -> Interval {
+    match *operand {
+        Operand::Value(value)           => Interval::exactly(value),
+        Operand::Register(ref register) => state.get(register).cloned().unwrap_or_else(|| Interval::exactly(0)),
+    }
+}
+
+/// If the comparision always, or never, holds for the values in `state`
+fn compare(state: &State, comparision: &Comparision) -> Option<bool> {
+    let (left, right) = (operand(state, &comparision.left), operand(state, &comparision.right));
+
+    // Anything compared with itself, such as `a < a`
+    let same = comparision.left == comparision.right;
+
+    let ordered = | below: bool, not_below: bool | if below { Some(true) } else if not_below { Some(false) } else { None };
+
+    match comparision.op {
+        ComparisionOperator::EQ | ComparisionOperator::NEQ => {
+            let equal = if same || (left.constant().is_some() && left.constant() == right.constant()) {
+                Some(true)
+            } else if left.max < right.min || right.max < left.min {
+                Some(false)
+            } else {
+                None
+            };
+
+            if comparision.op == ComparisionOperator::EQ { equal } else { equal.map(| equal | !equal) }
+        },
+        ComparisionOperator::LT  => ordered(!same && left.max < right.min, same || left.min >= right.max),
+        ComparisionOperator::LTE => ordered(same || left.max <= right.min, !same && left.min > right.max),
+        ComparisionOperator::GT  => ordered(!same && left.min > right.max, same || left.max <= right.min),
+        ComparisionOperator::GTE => ordered(same || left.min >= right.max, !same && left.max < right.min),
+    }
+}
+
+/// If the condition always, or never, holds for the values in `state`
+fn check(state: &State, condition: &Condition) -> Option<bool> {
+    match *condition {
+        Condition::Compare(ref comparision) => compare(state, comparision),
+        Condition::Not(ref inner)           => check(state, inner).map(| result | !result),
+        Condition::And(ref left, ref right) => match (check(state, left), check(state, right)) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true))            => Some(true),
+            _                                   => None,
+        },
+        Condition::Or(ref left, ref right)  => match (check(state, left), check(state, right)) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false))        => Some(false),
+            _                                 => None,
+        },
+    }
+}
+
+fn reads(operand: &Operand, read: &mut BTreeSet<String>) {
+    if let Some(register) = operand.register() {
+        read.insert(String::from(register));
+    }
+}
+
+/// Analyses a program, finding what holds however many times, and in whatever order, it's instructions run
+///
+/// A register's own value being used by `inc` and the like doesn't count as it being read.
+pub fn analyse(program: &[Instruction]) -> Analysis {
+    let mut read = BTreeSet::new();
+    let mut written = BTreeSet::new();
+
+    for instruction in program {
+        for comparision in instruction.condition.comparisions() {
+            reads(&comparision.left, &mut read);
+            reads(&comparision.right, &mut read);
+        }
+
+        for action in Some(&instruction.action).into_iter().chain(instruction.otherwise.as_ref()) {
+            reads(&action.value, &mut read);
+            written.insert(action.register.clone());
+        }
+    }
+
+    let mut ranges: State = read.union(&written).map(| register | (register.clone(), Interval::exactly(0))).collect();
+
+    // Keep applying every instruction which could run until the ranges stop growing
+    for pass in 1 .. {
+        let mut next = ranges.clone();
+
+        for instruction in program {
+            let taken: Vec<&Action> = match check(&ranges, &instruction.condition) {
+                Some(true)  => vec![&instruction.action],
+                Some(false) => instruction.otherwise.iter().collect(),
+                None        => Some(&instruction.action).into_iter().chain(instruction.otherwise.as_ref()).collect(),
+            };
+
+            for action in taken {
+                let result = ranges[&action.register].apply(action.op, operand(&ranges, &action.value));
+                let range = next.get_mut(&action.register).unwrap();
+                *range = range.join(result);
+            }
+        }
+
+        if pass > WIDEN_AFTER {
+            for (register, range) in &mut next {
+                let previous = ranges[register];
+
+                if range.min < previous.min { range.min = i32::MIN; }
+                if range.max > previous.max { range.max = i32::MAX; }
+            }
+        }
+
+        if next == ranges {
+            break;
+        }
+
+        ranges = next;
+    }
+
+    let constant_conditions = program.iter()
+        .enumerate()
+        .filter_map(| (index, instruction) | check(&ranges, &instruction.condition).map(| holds | (index, holds)))
+        .collect();
+
+    Analysis {
+        only_read: read.difference(&written).cloned().collect(),
+        only_written: written.difference(&read).cloned().collect(),
+        constant_conditions,
+        ranges,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use recorder::Recorder;
+
+    fn program(text: &str) -> Vec<Instruction> {
+        text.lines()
+            .filter(| line | !line.trim().is_empty())
+            .map(| line | line.parse().unwrap())
+            .collect()
+    }
+
+    fn names(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(| &name | String::from(name)).collect()
+    }
+
+    fn range(min: i32, max: i32) -> Interval { Interval { min, max } }
+
+    #[test]
+    fn test_example() {
+        let analysis = analyse(&program("
+            b inc 5 if a > 1
+            a inc 1 if b < 5
+            c dec -10 if a >= 1
+            c inc -20 if c == 10
+        "));
+
+        assert_eq!(analysis.only_read, BTreeSet::new());
+        assert_eq!(analysis.only_written, BTreeSet::new());
+        assert_eq!(analysis.constant_conditions, BTreeMap::new());
+        assert_eq!(analysis.ranges["a"], range(0, i32::MAX));
+        assert_eq!(analysis.ranges["b"], range(0, i32::MAX));
+        assert_eq!(analysis.ranges["c"], range(i32::MIN, i32::MAX));
+    }
+
+    #[test]
+    fn test_constant_conditions() {
+        let analysis = analyse(&program("
+            a inc 1 if x > 0
+            b set 3 if x == 0 else b set 4
+            c set y if a < 0
+            d inc 2 if z != 0 or b >= 3
+            e mul 7 if e > 0
+            f set 1 if f >= f
+        "));
+
+        assert_eq!(analysis.only_read, names(&["x", "y", "z"]));
+        assert_eq!(analysis.only_written, names(&["c", "d"]));
+        assert_eq!(
+            analysis.constant_conditions.into_iter().collect::<Vec<_>>(),
+            vec![(0, false), (1, true), (2, false), (4, false), (5, true)]
+        );
+
+        assert_eq!(analysis.ranges["a"], Interval::exactly(0));
+        assert_eq!(analysis.ranges["b"], range(0, 3));
+        assert_eq!(analysis.ranges["d"], range(0, i32::MAX));
+        assert_eq!(analysis.ranges["f"], range(0, 1));
+        assert_eq!(analysis.ranges["x"], Interval::exactly(0));
+    }
+
+    #[test]
+    fn test_intervals() {
+        assert_eq!(range(-3, 4).apply(Operation::MUL, range(-2, 5)), range(-15, 20));
+        assert_eq!(range(-7, 9).apply(Operation::DIV, range(-2, 3)), range(-9, 9));
+        assert_eq!(range(5, 9).apply(Operation::DIV, range(0, 2)), range(2, 9));
+        assert_eq!(range(5, 9).apply(Operation::DIV, range(0, 0)), range(5, 9));
+        assert_eq!(range(-20, 2).apply(Operation::MOD, range(-4, 3)), range(-3, 2));
+        assert_eq!(range(5, 9).apply(Operation::MOD, range(10, 10)), range(0, 9));
+        assert_eq!(range(i32::MAX - 1, i32::MAX).apply(Operation::INC, range(1, 1)), Interval::exactly(i32::MAX));
+    }
+
+    #[test]
+    fn test_bounds_input() {
+        let program = program(include_str!("input.txt"));
+        let analysis = analyse(&program);
+        let recorder = Recorder::run(&program).unwrap();
+
+        for (register, value) in recorder.registers().iter().chain(recorder.max_values()) {
+            assert!(analysis.ranges[register].contains(*value), "{} = {} outside {:?}", register, value, analysis.ranges[register]);
+        }
+
+        for (&index, &holds) in &analysis.constant_conditions {
+            assert_eq!(recorder.steps()[index].condition, holds, "Condition {} isn't constant", index);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+extern crate day_08;
+
+use std::io;
+use std::io::{BufRead, Write};
+
+use day_08::repl::Session;
+
+/// Reads instructions and commands a line at a time, running them against the same registers
+fn main() {
+    let mut session = Session::new();
+    let stdin = io::stdin();
+
+    println!("Enter instructions, or :help for the list of commands (end with EOF)");
+
+    loop {
+        print!("> ");
+        io::stdout().flush().expect("Unable to write prompt");
+
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0)    => break,
+            Ok(_)    => {},
+            Err(err) => { eprintln!("Unable to read input: {}", err); break; },
+        }
+
+        match session.handle(&line) {
+            Ok(ref output) if output.is_empty() => {},
+            Ok(output) => println!("{}", output),
+            Err(err)   => println!("Error: {}", err),
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use std::fmt;
 use std::str::FromStr;
 use std::collections::HashMap;
-use regex::Regex;
+
+use operand::Operand;
+use parser;
 
 /// Possible Conditions a program has
-#[derive(PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum ComparisionOperator {
     EQ,
     NEQ,
@@ -15,11 +18,20 @@ pub enum ComparisionOperator {
 }
 
 /// A comparision check
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Comparision {
-    pub register: String,
+    pub left: Operand,
     pub op: ComparisionOperator,
-    pub value: i32,
+    pub right: Operand,
+}
+
+/// A condition made up of comparisions joined with `and`, `or` and `not`
+#[derive(Clone, PartialEq)]
+pub enum Condition {
+    Compare(Comparision),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
 }
 
 
@@ -52,49 +64,137 @@ impl FromStr for ComparisionOperator {
     }
 }
 
-lazy_static! {
-    static ref COMPARISION_REGEX: Regex = Regex::new(
-    r"^([a-z]+) ([^\s]+) (-?[0-9]+)$"
-    ).expect("Unable to compile regex");
+impl ComparisionOperator {
+    pub fn compare(self, left: i32, right: i32) -> bool {
+        match self {
+            ComparisionOperator::EQ  => left == right,
+            ComparisionOperator::NEQ => left != right,
+            ComparisionOperator::LT  => left < right,
+            ComparisionOperator::LTE => left <= right,
+            ComparisionOperator::GT  => left > right,
+            ComparisionOperator::GTE => left >= right,
+        }
+    }
 }
 
 impl FromStr for Comparision {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        for cap in COMPARISION_REGEX.captures_iter(s.trim()) {
-            let op: ComparisionOperator = cap[2].parse().expect("Unable to parse op");
-            let value: i32 = cap[3].parse().expect("Unable to parse value");
-
-            return Ok(Comparision::new(&cap[1], op, value));
+        match parser::parse_condition(s)? {
+            Condition::Compare(comparision) => Ok(comparision),
+            _ => Err(format!("Unable to parse {}, expected a single comparision", s)),
         }
-
-        Err(format!("Unable to parse {}", s))
     }
 }
 
 impl fmt::Debug for Comparision {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {:?} {}", self.register, self.op, self.value)
+        write!(f, "{:?} {:?} {:?}", self.left, self.op, self.right)
     }
 }
 
 
 impl Comparision {
-    pub fn new(register: &str, op: ComparisionOperator, value: i32) -> Self {
-        Comparision { register: String::from(register), op, value }
+    pub fn new<L: Into<Operand>, R: Into<Operand>>(left: L, op: ComparisionOperator, right: R) -> Self {
+        Comparision { left: left.into(), op, right: right.into() }
     }
 
     pub fn check(&self, registers: &HashMap<String, i32>) -> bool {
-        let register: i32 = *registers.get(&self.register).unwrap_or(&0);
-
-        match self.op {
-            ComparisionOperator::EQ  => register == self.value,
-            ComparisionOperator::NEQ => register != self.value,
-            ComparisionOperator::LT  => register < self.value,
-            ComparisionOperator::LTE => register <= self.value,
-            ComparisionOperator::GT  => register > self.value,
-            ComparisionOperator::GTE => register >= self.value,
+        self.op.compare(self.left.value(registers), self.right.value(registers))
+    }
+}
+
+impl From<Comparision> for Condition {
+    fn from(comparision: Comparision) -> Self { Condition::Compare(comparision) }
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse_condition(s)
+    }
+}
+
+impl fmt::Debug for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Nested `and`s and `or`s are bracketed so the precedence is clear
+        let nested = | f: &mut fmt::Formatter, condition: &Condition | match *condition {
+            Condition::And(..) | Condition::Or(..) => write!(f, "({:?})", condition),
+            _ => write!(f, "{:?}", condition),
+        };
+
+        match *self {
+            Condition::Compare(ref comparision) => write!(f, "{:?}", comparision),
+            Condition::Not(ref inner) => {
+                write!(f, "not ")?;
+                nested(f, inner)
+            },
+            Condition::And(ref left, ref right) => {
+                nested(f, left)?;
+                write!(f, " and ")?;
+                nested(f, right)
+            },
+            Condition::Or(ref left, ref right) => {
+                nested(f, left)?;
+                write!(f, " or ")?;
+                nested(f, right)
+            },
         }
     }
 }
+
+impl Condition {
+    pub fn check(&self, registers: &HashMap<String, i32>) -> bool {
+        match *self {
+            Condition::Compare(ref comparision) => comparision.check(registers),
+            Condition::Not(ref inner)           => !inner.check(registers),
+            Condition::And(ref left, ref right) => left.check(registers) && right.check(registers),
+            Condition::Or(ref left, ref right)  => left.check(registers) || right.check(registers),
+        }
+    }
+
+    /// Every comparision in the condition, from left to right
+    pub fn comparisions(&self) -> Vec<&Comparision> {
+        match *self {
+            Condition::Compare(ref comparision) => vec![comparision],
+            Condition::Not(ref inner)           => inner.comparisions(),
+            Condition::And(ref left, ref right) |
+            Condition::Or(ref left, ref right)  => {
+                let mut comparisions = left.comparisions();
+                comparisions.extend(right.comparisions());
+                comparisions
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn registers(values: &[(&str, i32)]) -> HashMap<String, i32> {
+        values.iter().map(| &(name, value) | (String::from(name), value)).collect()
+    }
+
+    #[test]
+    fn test_check() {
+        let registers = registers(&[("a", 5), ("b", 7)]);
+        let check = | s: &str | s.parse::<Condition>().unwrap().check(&registers);
+
+        assert!(check("a < b"));
+        assert!(check("b >= 7 and a != b"));
+        assert!(!check("a > 5 or missing != 0"));
+        assert!(check("not a == b"));
+        assert!(check("not (a > 1 and b > 10) and missing == 0"));
+    }
+
+    #[test]
+    fn test_debug() {
+        let condition: Condition = "not (a > 1 or b < c) and d == -1".parse().unwrap();
+
+        assert_eq!(format!("{:?}", condition), "not (a > 1 or b < c) and d == -1");
+        assert_eq!(condition.comparisions().len(), 3);
+    }
+}
@@ -1,14 +1,21 @@
+use std::fmt;
 use std::str::FromStr;
 use std::collections::HashMap;
-use regex::Regex;
 
 use comparison::*;
+use operand::Operand;
+use parser;
 
 /// Possible operations a program has
-#[derive(Debug, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Operation {
     INC,
     DEC,
+    MUL,
+    DIV,
+    MOD,
+    SET,
 }
 
 impl FromStr for Operation {
@@ -18,68 +25,265 @@ impl FromStr for Operation {
         match s {
             "inc" => Ok(Operation::INC),
             "dec" => Ok(Operation::DEC),
+            "mul" => Ok(Operation::MUL),
+            "div" => Ok(Operation::DIV),
+            "mod" => Ok(Operation::MOD),
+            "set" => Ok(Operation::SET),
             _     => Err(String::from(s)),
         }
     }
 }
 
-/// An Instruction
-#[derive(Debug, PartialEq)]
-pub struct Instruction {
+impl Operation {
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::INC => "inc",
+            Operation::DEC => "dec",
+            Operation::MUL => "mul",
+            Operation::DIV => "div",
+            Operation::MOD => "mod",
+            Operation::SET => "set",
+        }
+    }
+
+    /// The new value of a register holding `current` after applying the operation with `value`
+    ///
+    /// Division rounds towards zero, and `mod` takes the sign of `current`. Dividing by zero, or
+    /// overflowing an `i32`, is an error.
+    pub fn apply(self, current: i32, value: i32) -> Result<i32, String> {
+        let result = match self {
+            Operation::INC => current.checked_add(value),
+            Operation::DEC => current.checked_sub(value),
+            Operation::MUL => current.checked_mul(value),
+            Operation::DIV => current.checked_div(value),
+            Operation::MOD => current.checked_rem(value),
+            Operation::SET => Some(value),
+        };
+
+        result.ok_or_else(||
+            if value == 0 && (self == Operation::DIV || self == Operation::MOD) {
+                format!("Unable to {} {} by zero", self.name(), current)
+            } else {
+                format!("Overflow when applying {} {} to {}", self.name(), value, current)
+            }
+        )
+    }
+}
+
+/// A change to a single register
+#[derive(Clone, PartialEq)]
+pub struct Action {
     pub register: String,
     pub op: Operation,
-    pub value: i32,
-    pub condition: Comparision,
+    pub value: Operand,
 }
 
-lazy_static! {
-    static ref INSTRUCTION_REGEX: Regex = Regex::new(
-    r"^([a-z]+) ([^\s]+) (-?[0-9]+) if (.*)$"
-    ).expect("Unable to compile regex");
+impl Action {
+    pub fn new<V: Into<Operand>>(register: &str, op: Operation, value: V) -> Self {
+        Action { register: String::from(register), op, value: value.into() }
+    }
+
+    /// The value the register will have after the action
+    pub fn result(&self, registers: &HashMap<String, i32>) -> Result<i32, String> {
+        let register = *registers.get(&self.register).unwrap_or(&0);
+
+        self.op.apply(register, self.value.value(registers))
+    }
 }
 
-impl FromStr for Instruction {
-    type Err = ();
+impl fmt::Debug for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {:?}", self.register, self.op.name(), self.value)
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        for cap in INSTRUCTION_REGEX.captures_iter(s.trim()) {
-            let op: Operation = cap[2].parse().expect("Unable to parse op");
-            let value: i32 = cap[3].parse().expect("Unable to parse value");
-            let condition: Comparision = cap[4].parse().expect("Unable to parse comparision");
-
-            return Ok(
-                Instruction::new(&cap[1], op, value, condition)
-            );
+/// A single change to a register
+#[derive(Clone, Debug, PartialEq)]
+pub struct Write {
+    pub register: String,
+    pub from: i32,
+    pub to: i32,
+}
+
+/// What happened when an instruction was executed
+#[derive(Clone, Debug, PartialEq)]
+pub struct Executed {
+    /// If the instruction's condition was met
+    pub condition: bool,
+
+    /// The write made, if the condition was met or the instruction has an `else`
+    pub write: Option<Write>,
+}
+
+/// An Instruction
+#[derive(Clone, PartialEq)]
+pub struct Instruction {
+    pub action: Action,
+    pub condition: Condition,
+
+    /// The action to take instead if the condition isn't met
+    pub otherwise: Option<Action>,
+}
+
+impl fmt::Debug for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} if {:?}", self.action, self.condition)?;
+
+        match self.otherwise {
+            Some(ref otherwise) => write!(f, " else {:?}", otherwise),
+            None                => Ok(()),
         }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = String;
 
-        Err(())
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse_instruction(s)
     }
 }
 
 impl Instruction {
-    pub fn new(register: &str, op: Operation, value: i32, condition: Comparision) -> Self {
+    pub fn new<V: Into<Operand>, C: Into<Condition>>(register: &str, op: Operation, value: V, condition: C) -> Self {
         Instruction {
-            register: String::from(register),
-            op, value, condition
+            action: Action::new(register, op, value),
+            condition: condition.into(),
+            otherwise: None,
+        }
+    }
+
+    /// Sets the action to take if the condition isn't met
+    pub fn otherwise(mut self, action: Action) -> Self {
+        self.otherwise = Some(action);
+        self
+    }
+
+    /// The action which will be taken with the current registers, if any
+    pub fn branch(&self, registers: &HashMap<String, i32>) -> Option<&Action> {
+        self.taken(self.condition.check(registers))
+    }
+
+    /// The action taken when the condition is, or isn't, met
+    fn taken(&self, condition: bool) -> Option<&Action> {
+        if condition {
+            Some(&self.action)
+        } else {
+            self.otherwise.as_ref()
         }
     }
 
-    pub fn execute(&self, registers: &mut HashMap<String, i32>, max_values: &mut HashMap<String, i32>) {
-        if self.condition.check(registers) {
-            let register = *registers.get(&self.register).unwrap_or(&0);
+    /// Runs the instruction, returning whether the condition was met and the write made. Nothing
+    /// is changed if it fails.
+    pub fn execute(&self, registers: &mut HashMap<String, i32>, max_values: &mut HashMap<String, i32>) -> Result<Executed, String> {
+        let condition = self.condition.check(registers);
+
+        let write = match self.taken(condition) {
+            Some(action) => {
+                let from = registers.get(&action.register).cloned().unwrap_or(0);
+                let new_value = action.result(registers)?;
+
+                let new_max = match max_values.get(&action.register) {
+                    Some(value) => new_value.max(*value),
+                    None        => new_value,
+                };
+
+                registers.insert(action.register.clone(), new_value);
+                max_values.insert(action.register.clone(), new_max);
+
+                Some(Write { register: action.register.clone(), from, to: new_value })
+            },
+            None => None,
+        };
+
+        Ok(Executed { condition, write })
+    }
+}
 
-            let new_value = match self.op {
-                Operation::INC => register + self.value,
-                Operation::DEC => register - self.value,
-            };
+#[cfg(test)]
+mod test {
+    use super::*;
 
-            let new_max = match max_values.get(&self.register) {
-                Some(value) => new_value.max(*value),
-                None        => new_value,
-            };
+    fn run(program: &str) -> Result<HashMap<String, i32>, String> {
+        let mut registers = HashMap::new();
+        let mut max_values = HashMap::new();
 
-            registers.insert(self.register.clone(), new_value);
-            max_values.insert(self.register.clone(), new_max);
+        for line in program.lines().filter(| line | !line.trim().is_empty()) {
+            line.parse::<Instruction>()?.execute(&mut registers, &mut max_values)?;
         }
+
+        Ok(registers)
+    }
+
+    #[test]
+    fn test_executed() {
+        let mut registers = HashMap::new();
+        let mut max_values = HashMap::new();
+        let instruction: Instruction = "a inc 5 if a == 0 else b dec 2".parse().unwrap();
+
+        assert_eq!(
+            instruction.execute(&mut registers, &mut max_values),
+            Ok(Executed { condition: true, write: Some(Write { register: String::from("a"), from: 0, to: 5 }) })
+        );
+        assert_eq!(
+            instruction.execute(&mut registers, &mut max_values),
+            Ok(Executed { condition: false, write: Some(Write { register: String::from("b"), from: 0, to: -2 }) })
+        );
+        assert_eq!(
+            "c inc 1 if a < 0".parse::<Instruction>().unwrap().execute(&mut registers, &mut max_values),
+            Ok(Executed { condition: false, write: None })
+        );
+        assert_eq!(max_values["b"], -2);
+    }
+
+    #[test]
+    fn test_operations() {
+        let registers = run("
+            a set 7 if a == 0
+            b set a if a == 7
+            a mul 3 if b > a
+            a mul b if b == 7
+            c div 2 if c == 0
+            b div 2 if b > 0
+            c set -7 if c == 0
+            c mod 3 if c < 0
+            b dec c if c == -1
+        ").unwrap();
+
+        assert_eq!(registers["a"], 49);
+        assert_eq!(registers["b"], 4);
+        assert_eq!(registers["c"], -1);
+    }
+
+    #[test]
+    fn test_else() {
+        let registers = run("
+            a inc 1 if b > 0 else b inc 10
+            a inc 1 if b > 0 else b inc 10
+            c set a if a > b or a == 1 else c set b
+        ").unwrap();
+
+        assert_eq!(registers["a"], 1);
+        assert_eq!(registers["b"], 10);
+        assert_eq!(registers["c"], 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(run("a div b if a == 0"), Err(String::from("Unable to div 0 by zero")));
+        assert_eq!(run("a mod 0 if a == 0"), Err(String::from("Unable to mod 0 by zero")));
+        assert_eq!(
+            run("a set 2147483647 if a == 0\na inc 1 if a > 0"),
+            Err(String::from("Overflow when applying inc 1 to 2147483647"))
+        );
+    }
+
+    #[test]
+    fn test_debug() {
+        let instruction = Instruction::new("a", Operation::MUL, "b", Comparision::new("c", ComparisionOperator::GT, 1))
+            .otherwise(Action::new("a", Operation::SET, -1));
+
+        assert_eq!(format!("{:?}", instruction), "a mul b if c > 1 else a set -1");
+        assert_eq!(format!("{:?}", instruction).parse::<Instruction>().unwrap(), instruction);
     }
 }
@@ -0,0 +1,8 @@
+pub mod analysis;
+pub mod comparison;
+pub mod instruction;
+pub mod operand;
+pub mod parser;
+pub mod recorder;
+pub mod repl;
+pub mod token;
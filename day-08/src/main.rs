@@ -1,46 +1,37 @@
-#[macro_use]
-extern crate lazy_static;
+extern crate day_08;
 
-extern crate regex;
-
-use std::collections::HashMap;
-
-mod instruction;
-mod comparison;
-
-use instruction::*;
-use comparison::*;
+use day_08::instruction::*;
+use day_08::recorder::Recorder;
 
 #[cfg(not(test))]
-const INPUT: &'static str = include_str!("input.txt");
+const INPUT: &str = include_str!("input.txt");
 
 #[cfg(not(test))]
 fn main() {
-    let mut map = HashMap::new();
-    let mut highest = HashMap::new();
-
-    INPUT.lines()
-        .for_each(
-            | line | line.parse::<Instruction>().unwrap()
-                .execute(&mut map, &mut highest)
-        );
+    let program: Vec<Instruction> = INPUT.lines().map(| line | line.parse().unwrap()).collect();
+    let recorder = Recorder::run(&program).unwrap();
 
     {
-        let (register, value) = map.iter().max_by_key(|t| t.1).unwrap();
+        let (register, value) = recorder.registers().iter().max_by_key(|t| t.1).unwrap();
         println!("Part 1: Register {} has the highest value {}", register, value);
     }
 
     {
-        let (register, value) = highest.iter().max_by_key(|t| t.1).unwrap();
-        println!("Part 2: Register {} had the highest value {}", register, value);
+        let (step, write) = recorder.all_time_max().unwrap();
+        println!(
+            "Part 2: Register {} had the highest value {}, set by line {}",
+            write.register, write.to, recorder.steps()[step].instruction + 1
+        );
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use std::collections::HashMap;
+    use day_08::comparison::*;
 
-    const TEST_INPUT: &'static str =
+    const TEST_INPUT: &str =
         "b inc 5 if a > 1
         a inc 1 if b < 5
         c dec -10 if a >= 1
@@ -86,8 +77,10 @@ mod test {
         TEST_INPUT
             .lines()
             .for_each(
-                | line | line.parse::<Instruction>().unwrap()
-                    .execute(&mut map, &mut highest)
+                | line | {
+                    line.parse::<Instruction>().unwrap()
+                        .execute(&mut map, &mut highest).unwrap();
+                }
             );
 
         {
@@ -99,5 +92,11 @@ mod test {
             let (register, value) = highest.iter().max_by_key(|t| t.1).unwrap();
             assert_eq!(10, *value, "Max Register During Process Wrong {}", register);
         }
+
+        let program: Vec<Instruction> = TEST_INPUT.lines().map(| line | line.parse().unwrap()).collect();
+        let recorder = Recorder::run(&program).unwrap();
+
+        assert_eq!(recorder.registers(), &map);
+        assert_eq!(recorder.max_values(), &highest);
     }
 }
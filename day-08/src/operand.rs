@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Either side of an operation or comparision
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Operand {
+    /// The current value of a register (registers start at 0)
+    Register(String),
+
+    Value(i32),
+}
+
+impl Operand {
+    pub fn value(&self, registers: &HashMap<String, i32>) -> i32 {
+        match *self {
+            Operand::Register(ref name) => *registers.get(name).unwrap_or(&0),
+            Operand::Value(value)       => value,
+        }
+    }
+
+    /// The name of the register this reads, if any
+    pub fn register(&self) -> Option<&str> {
+        match *self {
+            Operand::Register(ref name) => Some(name),
+            Operand::Value(_)           => None,
+        }
+    }
+}
+
+impl From<i32> for Operand {
+    fn from(value: i32) -> Self { Operand::Value(value) }
+}
+
+impl<'a> From<&'a str> for Operand {
+    fn from(register: &'a str) -> Self { Operand::Register(String::from(register)) }
+}
+
+impl fmt::Debug for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Operand::Register(ref name) => write!(f, "{}", name),
+            Operand::Value(value)       => write!(f, "{}", value),
+        }
+    }
+}
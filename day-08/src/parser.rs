@@ -0,0 +1,231 @@
+//! Parses instructions of the form
+//!
+//! ```text
+//! instruction := action "if" condition ["else" action]
+//! action      := register operation operand
+//! condition   := and {"or" and}
+//! and         := unary {"and" unary}
+//! unary       := "not" unary | "(" condition ")" | operand comparision operand
+//! operand     := register | number
+//! ```
+//!
+//! So `not` binds tightest, then `and`, then `or`.
+
+use comparison::{Comparision, Condition};
+use instruction::{Action, Instruction, Operation};
+use operand::Operand;
+use token::{tokenize, Token};
+
+/// Words which can't be used as register names
+const KEYWORDS: [&str; 5] = ["if", "else", "and", "or", "not"];
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+
+    /// The column just after the end of the input, for errors at the end
+    end: usize,
+}
+
+impl Parser {
+    fn new(input: &str) -> Result<Self, String> {
+        Ok(Parser { tokens: tokenize(input)?, position: 0, end: input.chars().count() + 1 })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(| (_, token) | token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).map(| (_, token) | token.clone());
+        self.position += 1;
+        token
+    }
+
+    /// An error about the token which was just read
+    fn error(&self, expected: &str) -> String {
+        match self.tokens.get(self.position - 1) {
+            Some(&(column, ref token)) => format!("Expected {} at column {}, found {:?}", expected, column, token),
+            None                       => format!("Expected {} at column {}, found the end", expected, self.end),
+        }
+    }
+
+    /// Moves past the next token if it is the given keyword
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word == keyword => {
+                self.position += 1;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn end(&mut self) -> Result<(), String> {
+        match self.next() {
+            None => Ok(()),
+            Some(_) => Err(self.error("the end")),
+        }
+    }
+
+    fn register(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Word(ref word)) if !KEYWORDS.contains(&word.as_str()) => Ok(word.clone()),
+            _ => Err(self.error("a register")),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, String> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Operand::Value(value)),
+            Some(Token::Word(ref word)) if !KEYWORDS.contains(&word.as_str()) => Ok(Operand::Register(word.clone())),
+            _ => Err(self.error("a register or number")),
+        }
+    }
+
+    fn action(&mut self) -> Result<Action, String> {
+        let register = self.register()?;
+
+        let op: Operation = match self.next() {
+            Some(Token::Word(ref word)) => word.parse().map_err(| _ | self.error("an operation"))?,
+            _ => return Err(self.error("an operation")),
+        };
+
+        Ok(Action { register, op, value: self.operand()? })
+    }
+
+    fn condition(&mut self) -> Result<Condition, String> {
+        let mut condition = self.and()?;
+
+        while self.keyword("or") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, String> {
+        let mut condition = self.unary()?;
+
+        while self.keyword("and") {
+            condition = Condition::And(Box::new(condition), Box::new(self.unary()?));
+        }
+
+        Ok(condition)
+    }
+
+    fn unary(&mut self) -> Result<Condition, String> {
+        if self.keyword("not") {
+            return Ok(Condition::Not(Box::new(self.unary()?)));
+        }
+
+        if self.peek() == Some(&Token::Open) {
+            self.position += 1;
+            let condition = self.condition()?;
+
+            return match self.next() {
+                Some(Token::Close) => Ok(condition),
+                _                  => Err(self.error("`)`")),
+            };
+        }
+
+        let left = self.operand()?;
+
+        let op = match self.next() {
+            Some(Token::Compare(op)) => op,
+            _                        => return Err(self.error("a comparision")),
+        };
+
+        Ok(Condition::Compare(Comparision { left, op, right: self.operand()? }))
+    }
+
+    fn instruction(&mut self) -> Result<Instruction, String> {
+        let action = self.action()?;
+
+        if !self.keyword("if") {
+            self.position += 1;
+            return Err(self.error("`if`"));
+        }
+
+        let condition = self.condition()?;
+
+        let otherwise = if self.keyword("else") { Some(self.action()?) } else { None };
+
+        Ok(Instruction { action, condition, otherwise })
+    }
+}
+
+pub fn parse_instruction(input: &str) -> Result<Instruction, String> {
+    let mut parser = Parser::new(input)?;
+    let instruction = parser.instruction()?;
+    parser.end()?;
+
+    Ok(instruction)
+}
+
+pub fn parse_condition(input: &str) -> Result<Condition, String> {
+    let mut parser = Parser::new(input)?;
+    let condition = parser.condition()?;
+    parser.end()?;
+
+    Ok(condition)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use comparison::ComparisionOperator;
+
+    fn compare(left: &str, op: ComparisionOperator, right: i32) -> Condition {
+        Condition::Compare(Comparision::new(left, op, right))
+    }
+
+    #[test]
+    fn test_precedence() {
+        let a = compare("a", ComparisionOperator::EQ, 1);
+        let b = compare("b", ComparisionOperator::EQ, 2);
+        let c = compare("c", ComparisionOperator::EQ, 3);
+
+        assert_eq!(
+            parse_condition("a == 1 or b == 2 and c == 3").unwrap(),
+            Condition::Or(Box::new(a.clone()), Box::new(Condition::And(Box::new(b.clone()), Box::new(c.clone()))))
+        );
+        assert_eq!(
+            parse_condition("(a == 1 or b == 2) and not c == 3").unwrap(),
+            Condition::And(
+                Box::new(Condition::Or(Box::new(a.clone()), Box::new(b.clone()))),
+                Box::new(Condition::Not(Box::new(c.clone())))
+            )
+        );
+        assert_eq!(
+            parse_condition("not not a == 1").unwrap(),
+            Condition::Not(Box::new(Condition::Not(Box::new(a))))
+        );
+    }
+
+    #[test]
+    fn test_instruction() {
+        assert_eq!(
+            parse_instruction("a set b if 1 < c else d mod -3").unwrap(),
+            Instruction {
+                action: Action::new("a", Operation::SET, "b"),
+                condition: Condition::Compare(Comparision::new(1, ComparisionOperator::LT, "c")),
+                otherwise: Some(Action::new("d", Operation::MOD, -3)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = | input | parse_instruction(input).unwrap_err();
+
+        assert_eq!(error("a jump 1 if a > 1"), "Expected an operation at column 3, found Word(\"jump\")");
+        assert_eq!(error("a inc 1 when a > 1"), "Expected `if` at column 9, found Word(\"when\")");
+        assert_eq!(error("a inc 1 if a > 1 and"), "Expected a register or number at column 21, found the end");
+        assert_eq!(error("a inc 1 if (a > 1"), "Expected `)` at column 18, found the end");
+        assert_eq!(error("a inc 1 if a > 1 b"), "Expected the end at column 18, found Word(\"b\")");
+        assert_eq!(error("if inc 1 if a > 1"), "Expected a register at column 1, found Word(\"if\")");
+        assert_eq!(error("a inc 1 if a b"), "Expected a comparision at column 14, found Word(\"b\")");
+        assert_eq!(error("a inc 1"), "Expected `if` at column 8, found the end");
+    }
+}
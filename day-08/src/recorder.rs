@@ -0,0 +1,212 @@
+use std::collections::{BTreeMap, HashMap};
+
+use instruction::Instruction;
+
+pub use instruction::Write;
+
+/// What happened when an instruction was executed
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step {
+    /// The index of the instruction in it's program
+    pub instruction: usize,
+
+    /// If the instruction's condition was met
+    pub condition: bool,
+
+    /// The write made, if the condition was met or the instruction has an `else`
+    pub write: Option<Write>,
+}
+
+/// How many times an instruction's condition was, and wasn't, met
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Fired {
+    pub met: usize,
+    pub not_met: usize,
+}
+
+/// Executes instructions while keeping a record of every step taken
+///
+/// Steps are numbered from 0 in the order they are recorded.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    registers: HashMap<String, i32>,
+    max_values: HashMap<String, i32>,
+    steps: Vec<Step>,
+
+    /// The steps which wrote to each register, in order
+    writes: HashMap<String, Vec<usize>>,
+}
+
+impl Recorder {
+    pub fn new() -> Self { Recorder::default() }
+
+    /// Records a whole program, with each instruction's index being it's position in the program
+    pub fn run(program: &[Instruction]) -> Result<Self, String> {
+        let mut recorder = Recorder::new();
+
+        for (index, instruction) in program.iter().enumerate() {
+            recorder.record(index, instruction)?;
+        }
+
+        Ok(recorder)
+    }
+
+    pub fn registers(&self) -> &HashMap<String, i32> { &self.registers }
+
+    pub fn max_values(&self) -> &HashMap<String, i32> { &self.max_values }
+
+    pub fn steps(&self) -> &[Step] { &self.steps }
+
+    /// Executes the instruction at `index`, recording the step. Nothing is recorded if it fails.
+    pub fn record(&mut self, index: usize, instruction: &Instruction) -> Result<&Step, String> {
+        let executed = instruction.execute(&mut self.registers, &mut self.max_values)?;
+
+        if let Some(ref write) = executed.write {
+            self.writes.entry(write.register.clone()).or_default().push(self.steps.len());
+        }
+
+        self.steps.push(Step { instruction: index, condition: executed.condition, write: executed.write });
+
+        Ok(&self.steps[self.steps.len() - 1])
+    }
+
+    /// Every write made to the register, along with the step which made it
+    pub fn writes(&self, register: &str) -> Vec<(usize, &Write)> {
+        self.writes.get(register)
+            .map(| steps | steps.iter().filter_map(| &step | self.write(step).map(| write | (step, write))).collect())
+            .unwrap_or_default()
+    }
+
+    /// The value the register held just after the given step
+    pub fn value_after(&self, register: &str, step: usize) -> i32 {
+        let steps = match self.writes.get(register) {
+            Some(steps) => steps,
+            None        => return 0,
+        };
+
+        match steps.partition_point(| &written | written <= step) {
+            0     => 0,
+            count => self.write(steps[count - 1]).map_or(0, | write | write.to),
+        }
+    }
+
+    /// The first write of the highest value any register has held, along with the step which made it
+    pub fn all_time_max(&self) -> Option<(usize, &Write)> {
+        let mut max: Option<(usize, &Write)> = None;
+
+        for (step, write) in (0 .. self.steps.len()).filter_map(| step | self.write(step).map(| write | (step, write))) {
+            if max.is_none_or(| (_, best) | write.to > best.to) {
+                max = Some((step, write));
+            }
+        }
+
+        max
+    }
+
+    /// How many times each instruction's condition was met, by the instruction's index
+    pub fn fired(&self) -> BTreeMap<usize, Fired> {
+        let mut fired: BTreeMap<usize, Fired> = BTreeMap::new();
+
+        for step in &self.steps {
+            let counts = fired.entry(step.instruction).or_default();
+
+            if step.condition {
+                counts.met += 1;
+            } else {
+                counts.not_met += 1;
+            }
+        }
+
+        fired
+    }
+
+    fn write(&self, step: usize) -> Option<&Write> {
+        self.steps.get(step).and_then(| step | step.write.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn program(text: &str) -> Vec<Instruction> {
+        text.lines()
+            .filter(| line | !line.trim().is_empty())
+            .map(| line | line.parse().unwrap())
+            .collect()
+    }
+
+    fn example() -> Recorder {
+        Recorder::run(&program("
+            b inc 5 if a > 1
+            a inc 1 if b < 5
+            c dec -10 if a >= 1
+            c inc -20 if c == 10
+        ")).unwrap()
+    }
+
+    #[test]
+    fn test_steps() {
+        let recorder = example();
+
+        assert_eq!(recorder.steps().len(), 4);
+        assert_eq!(recorder.steps()[0], Step { instruction: 0, condition: false, write: None });
+        assert_eq!(
+            recorder.steps()[3],
+            Step { instruction: 3, condition: true, write: Some(Write { register: String::from("c"), from: 10, to: -10 }) }
+        );
+        assert_eq!(recorder.writes("c").iter().map(| &(step, write) | (step, write.to)).collect::<Vec<_>>(), vec![(2, 10), (3, -10)]);
+        assert!(recorder.writes("b").is_empty());
+    }
+
+    #[test]
+    fn test_value_after() {
+        let recorder = example();
+
+        assert_eq!(recorder.value_after("c", 1), 0);
+        assert_eq!(recorder.value_after("c", 2), 10);
+        assert_eq!(recorder.value_after("c", 3), -10);
+        assert_eq!(recorder.value_after("c", 100), -10);
+        assert_eq!(recorder.value_after("b", 3), 0);
+    }
+
+    #[test]
+    fn test_all_time_max() {
+        let recorder = example();
+        let (step, write) = recorder.all_time_max().unwrap();
+
+        assert_eq!(step, 2);
+        assert_eq!(write.register, "c");
+        assert_eq!(write.to, 10);
+
+        assert_eq!(Recorder::new().all_time_max(), None);
+
+        // Ties go to the first write
+        let recorder = Recorder::run(&program("a set 3 if a == 0\nb set 3 if a == 3")).unwrap();
+        assert_eq!(recorder.all_time_max().unwrap().1.register, "a");
+    }
+
+    #[test]
+    fn test_fired() {
+        let mut recorder = Recorder::new();
+        let instructions = program("a inc 1 if a < 2 else b inc 1\nc inc 1 if b > 0");
+
+        for _ in 0 .. 3 {
+            for (index, instruction) in instructions.iter().enumerate() {
+                recorder.record(index, instruction).unwrap();
+            }
+        }
+
+        assert_eq!(recorder.fired()[&0], Fired { met: 2, not_met: 1 });
+        assert_eq!(recorder.fired()[&1], Fired { met: 1, not_met: 2 });
+        assert_eq!(recorder.registers()["b"], 1);
+    }
+
+    #[test]
+    fn test_failed_step() {
+        let mut recorder = Recorder::new();
+
+        assert!(recorder.record(0, &"a div 0 if a == 0".parse().unwrap()).is_err());
+        assert!(recorder.steps().is_empty());
+    }
+}
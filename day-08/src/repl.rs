@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::fs;
+
+use instruction::Instruction;
+use recorder::Recorder;
+
+const HELP: &str = "\
+Enter an instruction to run it, such as `a inc 5 if b < 1`, or one of the commands:
+  :regs           show the value of every register
+  :max            show the highest value currently held, and ever held
+  :history reg    show every value written to the register
+  :reset          set every register back to 0 and forget their history
+  :load file      run every instruction in the file
+  :help           show this message";
+
+/// The registers shared by every line entered into the REPL
+#[derive(Debug, Default)]
+pub struct Session {
+    recorder: Recorder,
+}
+
+impl Session {
+    pub fn new() -> Self { Session::default() }
+
+    pub fn registers(&self) -> &HashMap<String, i32> { self.recorder.registers() }
+
+    /// Handles a line of input, returning the text to show
+    pub fn handle(&mut self, line: &str) -> Result<String, String> {
+        let line = line.trim();
+
+        if line.is_empty() {
+            return Ok(String::new());
+        }
+
+        if !line.starts_with(':') {
+            return self.run(line);
+        }
+
+        let mut words = line[1 ..].split_whitespace();
+
+        match (words.next(), words.next(), words.next()) {
+            (Some("regs"), None, _)              => Ok(self.show_registers()),
+            (Some("max"), None, _)               => Ok(self.show_max()),
+            (Some("history"), Some(register), None) => Ok(self.show_history(register)),
+            (Some("reset"), None, _)             => {
+                *self = Session::default();
+                Ok(String::from("Registers reset"))
+            },
+            (Some("load"), Some(_), _)           => self.load(line[1 ..].trim_start()["load".len() ..].trim()),
+            (Some("help"), None, _)              => Ok(String::from(HELP)),
+            _ => Err(format!("Unknown command `{}`, try :help", line)),
+        }
+    }
+
+    /// Runs a single instruction, showing the register it changed
+    fn run(&mut self, line: &str) -> Result<String, String> {
+        let instruction: Instruction = line.parse()?;
+
+        let index = self.recorder.steps().len();
+
+        match self.recorder.record(index, &instruction)?.write {
+            Some(ref write) => Ok(format!("{} = {}", write.register, write.to)),
+            None            => Ok(String::from("Condition not met")),
+        }
+    }
+
+    /// Runs every instruction in a file, stopping at the first which fails
+    fn load(&mut self, path: &str) -> Result<String, String> {
+        let program = fs::read_to_string(path).map_err(| err | format!("Unable to read {}: {}", path, err))?;
+        let mut count = 0;
+
+        for (i, line) in program.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            self.run(line).map_err(| err | format!("{} line {}: {}", path, i + 1, err))?;
+            count += 1;
+        }
+
+        Ok(format!("Ran {} instructions from {}", count, path))
+    }
+
+    fn show_registers(&self) -> String {
+        if self.registers().is_empty() {
+            return String::from("Every register is 0");
+        }
+
+        let mut registers: Vec<(&String, &i32)> = self.registers().iter().collect();
+        registers.sort();
+
+        registers.iter()
+            .map(| &(name, value) | format!("{} = {}", name, value))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn show_max(&self) -> String {
+        // Ties go to the first register alphabetically
+        fn largest(values: &HashMap<String, i32>) -> Option<(&String, &i32)> {
+            values.iter().max_by_key(| &(name, value) | (*value, std::cmp::Reverse(name)))
+        }
+
+        match (largest(self.recorder.registers()), largest(self.recorder.max_values())) {
+            (Some((name, value)), Some((max_name, max_value))) =>
+                format!("Highest now: {} = {}\nHighest ever: {} = {}", name, value, max_name, max_value),
+            _ => String::from("Every register is 0"),
+        }
+    }
+
+    fn show_history(&self, register: &str) -> String {
+        let writes = self.recorder.writes(register);
+
+        if writes.is_empty() {
+            return format!("{} has never been written", register);
+        }
+
+        format!(
+            "{}: 0 -> {}",
+            register,
+            writes.iter().map(| &(_, write) | write.to.to_string()).collect::<Vec<String>>().join(" -> ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_instructions() {
+        let mut session = Session::new();
+
+        assert_eq!(session.handle("b inc 5 if a > 1"), Ok(String::from("Condition not met")));
+        assert_eq!(session.handle("a inc 1 if b < 5"), Ok(String::from("a = 1")));
+        assert_eq!(session.handle("c dec -10 if a >= 1"), Ok(String::from("c = 10")));
+        assert_eq!(session.handle("c inc -20 if c == 10"), Ok(String::from("c = -10")));
+        assert_eq!(session.handle("  "), Ok(String::new()));
+
+        assert_eq!(session.handle(":regs"), Ok(String::from("a = 1\nc = -10")));
+        assert_eq!(session.handle(":max"), Ok(String::from("Highest now: a = 1\nHighest ever: c = 10")));
+        assert_eq!(session.handle(":history c"), Ok(String::from("c: 0 -> 10 -> -10")));
+        assert_eq!(session.handle(":history b"), Ok(String::from("b has never been written")));
+
+        assert_eq!(session.handle(":reset"), Ok(String::from("Registers reset")));
+        assert_eq!(session.handle(":regs"), Ok(String::from("Every register is 0")));
+        assert_eq!(session.handle(":max"), Ok(String::from("Every register is 0")));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut session = Session::new();
+
+        assert!(session.handle("a inc").is_err());
+        assert!(session.handle("a div 0 if a == 0").is_err());
+        assert_eq!(session.handle(":history"), Err(String::from("Unknown command `:history`, try :help")));
+        assert_eq!(session.handle(":jump"), Err(String::from("Unknown command `:jump`, try :help")));
+        assert!(session.handle(":load /this/does/not/exist").is_err());
+        assert!(session.registers().is_empty());
+    }
+
+    #[test]
+    fn test_load() {
+        let path = env::temp_dir().join(format!("day-08-repl-{}.txt", std::process::id()));
+        fs::write(&path, "a inc 1 if b < 5\n\nb set a if a == 1\nb fly 1 if a == 1\n").unwrap();
+
+        let mut session = Session::new();
+        let result = session.handle(&format!(":load {}", path.display()));
+
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(result, Err(format!("{} line 4: Expected an operation at column 3, found Word(\"fly\")", path.display())));
+        assert_eq!(session.handle(":regs"), Ok(String::from("a = 1\nb = 1")));
+    }
+}
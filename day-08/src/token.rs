@@ -0,0 +1,101 @@
+use comparison::ComparisionOperator;
+
+/// A single token of an instruction
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    /// A register name, operation or keyword
+    Word(String),
+
+    Number(i32),
+    Compare(ComparisionOperator),
+    Open,
+    Close,
+}
+
+/// Splits an instruction into it's tokens, along with the column (starting at 1) each starts at
+pub fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            },
+            '(' => { i += 1; Token::Open },
+            ')' => { i += 1; Token::Close },
+            '=' | '!' | '<' | '>' => {
+                i += 1;
+                if chars.get(i) == Some(&'=') {
+                    i += 1;
+                }
+
+                let op: String = chars[start .. i].iter().collect();
+                Token::Compare(op.parse().map_err(| _ | format!("Unknown operator `{}` at column {}", op, start + 1))?)
+            },
+            c if c == '-' || c.is_ascii_digit() => {
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+
+                let number: String = chars[start .. i].iter().collect();
+                Token::Number(number.parse().map_err(| _ | format!("Invalid number `{}` at column {}", number, start + 1))?)
+            },
+            c if c.is_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+
+                Token::Word(chars[start .. i].iter().collect())
+            },
+            c => return Err(format!("Unexpected character `{}` at column {}", c, start + 1)),
+        };
+
+        tokens.push((start + 1, token));
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<Token> {
+        tokenize(input).unwrap().into_iter().map(| (_, token) | token).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokens("b inc -5 if (a>=1)"),
+            vec![
+                Token::Word(String::from("b")),
+                Token::Word(String::from("inc")),
+                Token::Number(-5),
+                Token::Word(String::from("if")),
+                Token::Open,
+                Token::Word(String::from("a")),
+                Token::Compare(ComparisionOperator::GTE),
+                Token::Number(1),
+                Token::Close,
+            ]
+        );
+
+        assert_eq!(tokenize("a  == b").unwrap().iter().map(| &(column, _) | column).collect::<Vec<usize>>(), vec![1, 4, 7]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(tokenize("a inc 1 if a = 1"), Err(String::from("Unknown operator `=` at column 14")));
+        assert_eq!(tokenize("a inc - if"), Err(String::from("Invalid number `-` at column 7")));
+        assert_eq!(tokenize("a inc 99999999999"), Err(String::from("Invalid number `99999999999` at column 7")));
+        assert_eq!(tokenize("a inc 1 if a == 1;"), Err(String::from("Unexpected character `;` at column 18")));
+    }
+}
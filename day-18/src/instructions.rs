@@ -17,8 +17,16 @@ pub fn get_register(register: RegisterName, registers: &Registers) -> i64 {
     registers.get(&register).map( | i | *i).unwrap_or(0)
 }
 
+/// Updates a `register` using it's current value and the given `value` using the function `f`
+pub fn update_register<T>(registers: &mut Registers, register: RegisterName, value: &Value, f: T)
+    where T: Fn(i64, i64) -> i64
+{
+    let value = f(get_register(register, registers), value.get(registers));
+    registers.insert(register, value);
+}
+
 /// Parses a string into a register name
-fn parse_register_name(input: &str) -> Result<RegisterName, String> {
+pub fn parse_register_name(input: &str) -> Result<RegisterName, String> {
     if input.trim().len() != 1 {
         Err(format!("Expected only one character, got input `{}`", input))
     } else {
@@ -66,7 +74,7 @@ pub enum Instruction {
     Jump(Value, Value),
 }
 
-fn parse_register_value_pair(r: &str, v: &str, f: &Fn(RegisterName, Value) -> Instruction) -> Result<Instruction, String> {
+fn parse_register_value_pair(r: &str, v: &str, f: &dyn Fn(RegisterName, Value) -> Instruction) -> Result<Instruction, String> {
     parse_register_name(r).and_then(| r | { Value::from_str(v).map( | v | f(r, v)) })
 }
 
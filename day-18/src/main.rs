@@ -1,6 +1,9 @@
+extern crate day_18;
+
 use std::thread;
 
-mod instructions;
+use day_18::instructions;
+use instructions::update_register;
 
 #[cfg(not(test))]
 fn main() {
@@ -11,17 +14,6 @@ fn main() {
     println!("Part 2: {}", run_threads(program));
 }
 
-/// Updates a `register` using it's current value and the given `value` using the function `f`
-fn update_register<T>(
-    registers: &mut instructions::Registers,
-    register: instructions::RegisterName, value: &instructions::Value,
-    f: T
-) where T: Fn(i64, i64) -> i64
-{
-    let value = f(instructions::get_register(register, registers), value.get(registers));
-    registers.insert(register, value);
-}
-
 /// Finds the first "recovered" value when the instructions are run
 fn find_first_recovered(program: &Vec<instructions::Instruction>) -> i64 {
     use instructions::Registers;
@@ -0,0 +1,160 @@
+extern crate utils;
+
+use std::collections::HashMap;
+use utils::grid::{Direction, Position};
+
+#[cfg(not(test))]
+fn main() {
+    let input = utils::read_all_input();
+
+    println!("Part 1: {}", infections_caused(&input, 10_000, Rules::Simple).unwrap());
+    println!("Part 2: {}", infections_caused(&input, 10_000_000, Rules::Evolved).unwrap());
+}
+
+/// The state of a single node within the cluster
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum NodeState {
+    Clean,
+    Weakened,
+    Infected,
+    Flagged,
+}
+
+impl NodeState {
+    /// Turns the virus carrier based on the state of the node it is currently on
+    fn turn(self, direction: Direction) -> Direction {
+        match self {
+            NodeState::Clean    => direction.turn_left(),
+            NodeState::Weakened => direction,
+            NodeState::Infected => direction.turn_right(),
+            NodeState::Flagged  => direction.reverse(),
+        }
+    }
+}
+
+/// The rules the virus carrier follows when it modifies a node
+#[derive(Clone, Copy)]
+enum Rules {
+    /// Nodes toggle between `Clean` and `Infected`
+    Simple,
+
+    /// Nodes cycle through `Clean` -> `Weakened` -> `Infected` -> `Flagged` -> `Clean`
+    Evolved,
+}
+
+impl Rules {
+    /// The state a node will be left in after the virus carrier has visited it
+    fn next_state(self, state: NodeState) -> NodeState {
+        match (self, state) {
+            (Rules::Simple, NodeState::Clean)     => NodeState::Infected,
+            (Rules::Simple, _)                    => NodeState::Clean,
+            (Rules::Evolved, NodeState::Clean)    => NodeState::Weakened,
+            (Rules::Evolved, NodeState::Weakened) => NodeState::Infected,
+            (Rules::Evolved, NodeState::Infected) => NodeState::Flagged,
+            (Rules::Evolved, NodeState::Flagged)  => NodeState::Clean,
+        }
+    }
+}
+
+/// Parses the input grid into a map of nodes which are not clean, with the middle of the grid at
+/// the origin
+///
+/// # Example
+///
+/// ```text
+/// ..#
+/// #..
+/// ...
+/// ```
+///
+/// Gives infected nodes at `(1, 1)` and `(-1, 0)`
+fn parse(input: &str) -> Result<HashMap<Position, NodeState>, String> {
+    let rows: Vec<&str> = input.trim().lines().map(| line | line.trim()).collect();
+    let centre = (rows.len() / 2) as i64;
+
+    let mut nodes = HashMap::new();
+
+    for (row, line) in rows.iter().enumerate() {
+        for (col, c) in line.chars().enumerate() {
+            let position = Position::new(col as i64 - centre, centre - row as i64);
+
+            match c {
+                '#' => { nodes.insert(position, NodeState::Infected); },
+                '.' => (),
+                _   => return Err(format!("Unknown node `{}` at line {}, column {}", c, row + 1, col + 1)),
+            }
+        }
+    }
+
+    Ok(nodes)
+}
+
+/// Runs the virus carrier for the given number of `bursts` and returns how many of those bursts
+/// caused a node to become infected
+fn infections_caused(input: &str, bursts: usize, rules: Rules) -> Result<usize, String> {
+    let mut nodes = parse(input)?;
+    let mut position = Position::default();
+    let mut direction = Direction::Up;
+    let mut infections = 0;
+
+    for _ in 0 .. bursts {
+        let state = nodes.get(&position).cloned().unwrap_or(NodeState::Clean);
+        let new_state = rules.next_state(state);
+
+        direction = state.turn(direction);
+
+        if new_state == NodeState::Clean {
+            nodes.remove(&position);
+        } else {
+            if new_state == NodeState::Infected {
+                infections += 1;
+            }
+
+            nodes.insert(position, new_state);
+        }
+
+        position = position.step(direction);
+    }
+
+    Ok(infections)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "
+        ..#
+        #..
+        ...
+    ";
+
+    #[test]
+    fn test_parse() {
+        let nodes = parse(INPUT).unwrap();
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes.get(&Position::new(1, 1)), Some(&NodeState::Infected));
+        assert_eq!(nodes.get(&Position::new(-1, 0)), Some(&NodeState::Infected));
+
+        assert_eq!(parse("..#\n#x.\n..."), Err(String::from("Unknown node `x` at line 2, column 2")));
+    }
+
+    #[test]
+    fn test_simple_rules() {
+        assert_eq!(infections_caused(INPUT, 7, Rules::Simple).unwrap(), 5);
+        assert_eq!(infections_caused(INPUT, 70, Rules::Simple).unwrap(), 41);
+        assert_eq!(infections_caused(INPUT, 10_000, Rules::Simple).unwrap(), 5587);
+    }
+
+    #[test]
+    fn test_evolved_rules() {
+        assert_eq!(infections_caused(INPUT, 100, Rules::Evolved).unwrap(), 26);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_evolved_rules_full() {
+        assert_eq!(infections_caused(INPUT, 10_000_000, Rules::Evolved).unwrap(), 2_511_944);
+    }
+}
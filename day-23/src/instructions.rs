@@ -0,0 +1,41 @@
+use std::str::FromStr;
+
+use day_18::instructions::{parse_register_name, RegisterName, Value};
+
+/// Parses an input string into a vector of instructions
+pub fn parse(s: &str) -> Vec<Instruction> {
+    s.trim().lines().map(| l | l.parse().unwrap()).collect()
+}
+
+/// The instructions supported by the coprocessor
+#[derive(Clone, Debug, PartialEq)]
+pub enum Instruction {
+    Set(RegisterName, Value),
+    Subtract(RegisterName, Value),
+    Multiply(RegisterName, Value),
+    JumpNotZero(Value, Value),
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+
+        if parts.len() != 3 {
+            return Err(format!("Expected three parts to instruction: `{}`", s));
+        }
+
+        let register_value_pair = | f: fn(RegisterName, Value) -> Instruction | {
+            parse_register_name(parts[1]).and_then(| r | parts[2].parse().map(| v | f(r, v)))
+        };
+
+        match parts[0] {
+            "set" => register_value_pair(Instruction::Set),
+            "sub" => register_value_pair(Instruction::Subtract),
+            "mul" => register_value_pair(Instruction::Multiply),
+            "jnz" => parts[1].parse().and_then(| x | parts[2].parse().map(| y | Instruction::JumpNotZero(x, y))),
+            _     => Err(format!("Unknown op code `{}`", parts[0])),
+        }
+    }
+}
@@ -0,0 +1,247 @@
+extern crate day_18;
+extern crate utils;
+
+mod instructions;
+
+use day_18::instructions::{update_register, Registers, Value};
+use instructions::Instruction;
+
+#[cfg(not(test))]
+fn main() {
+    let program = instructions::parse(&utils::read_all_input());
+
+    println!("Part 1: {}", run(&program, Registers::new()).1);
+    println!("Part 2: {}", optimised_h(&program).unwrap());
+}
+
+/// Runs the `program` from the given starting `registers` until it jumps outside of the
+/// instructions, returning the final registers and how many times `mul` was executed
+fn run(program: &[Instruction], registers: Registers) -> (Registers, usize) {
+    use instructions::Instruction::*;
+
+    let mut registers = registers;
+    let mut program_counter: i64 = 0;
+    let mut multiplies = 0;
+
+    while program_counter >= 0 {
+        let instruction = match program.get(program_counter as usize) {
+            Some(instruction) => instruction,
+            None              => break,
+        };
+
+        match *instruction {
+            Set(register, ref value)      => update_register(&mut registers, register, value, | _, v | v),
+            Subtract(register, ref value) => update_register(&mut registers, register, value, | r, v | r - v),
+            Multiply(register, ref value) => {
+                multiplies += 1;
+                update_register(&mut registers, register, value, | r, v | r * v)
+            },
+            JumpNotZero(ref x, ref y)     => if x.get(&registers) != 0 { program_counter += y.get(&registers) - 1; },
+        }
+
+        program_counter += 1;
+    }
+
+    (registers, multiplies)
+}
+
+/// Works out the value the program leaves in register `h` when it is run with `a` set to `1`,
+/// without running the (very slow) program itself.
+///
+/// Annotated, the program is:
+///
+/// ```
+///     set b 57          b = 57
+///     set c b           c = b
+///     jnz a 2           if a != 0 {
+///     jnz 1 5
+///     mul b 100             b = b * 100 + 100000
+///     sub b -100000
+///     set c b               c = b + 17000
+///     sub c -17000      }
+///     set f 1           loop {
+///     set d 2               f = 1
+///     set e 2               for d in 2 .. b {
+///     set g d                   for e in 2 .. b {
+///     mul g e                       if d * e == b {
+///     sub g b                           f = 0
+///     jnz g 2                       }
+///     set f 0                   }
+///     sub e -1              }
+///     set g e
+///     sub g b               if f == 0 {
+///     jnz g -8                  h += 1
+///     sub d -1              }
+///     set g d
+///     sub g b               if b == c {
+///     jnz g -13                 break
+///     jnz f 2               }
+///     sub h -1
+///     set g b               b += 17
+///     sub g c           }
+///     jnz g 2
+///     jnz 1 3
+///     sub b -17
+///     jnz 1 -23
+/// ```
+///
+/// So `h` ends up as the number of composite numbers from `b` to `c` (inclusive), stepping by
+/// the amount `b` is increased by at the end of the loop.
+fn optimised_h(program: &[Instruction]) -> Result<usize, String> {
+    use instructions::Instruction::*;
+
+    // Run the set up (everything before the first `set f 1`) to find the range being checked
+    let loop_start = program.iter()
+        .position(| i | *i == Set('f', Value::Value(1)))
+        .ok_or("Unable to find the start of the main loop")?;
+
+    let mut registers = Registers::new();
+    registers.insert('a', 1);
+    let (registers, _) = run(&program[.. loop_start], registers);
+
+    let b = registers.get(&'b').cloned().unwrap_or(0);
+    let c = registers.get(&'c').cloned().unwrap_or(0);
+
+    // The last change to `b` is the step between each number checked
+    let step = program.iter().rev()
+        .filter_map(| i | match *i {
+            Subtract('b', Value::Value(v)) => Some(-v),
+            _                              => None,
+        })
+        .next()
+        .ok_or("Unable to find the step size for `b`")?;
+
+    if step <= 0 {
+        return Err(format!("Expected `b` to increase at the end of the loop, but it changes by {}", step));
+    }
+
+    Ok(
+        (b ..= c).step_by(step as usize)
+            .filter(| n | !is_prime(*n))
+            .count()
+    )
+}
+
+/// Checks if the given number is prime using trial division
+fn is_prime(n: i64) -> bool {
+    if n < 2 {
+        return false;
+    }
+
+    let mut divisor = 2;
+
+    while divisor * divisor <= n {
+        if n % divisor == 0 {
+            return false;
+        }
+
+        divisor += 1;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "
+        set b 57
+        set c b
+        jnz a 2
+        jnz 1 5
+        mul b 100
+        sub b -100000
+        set c b
+        sub c -17000
+        set f 1
+        set d 2
+        set e 2
+        set g d
+        mul g e
+        sub g b
+        jnz g 2
+        set f 0
+        sub e -1
+        set g e
+        sub g b
+        jnz g -8
+        sub d -1
+        set g d
+        sub g b
+        jnz g -13
+        jnz f 2
+        sub h -1
+        set g b
+        sub g c
+        jnz g 2
+        jnz 1 3
+        sub b -17
+        jnz 1 -23
+    ";
+
+    #[test]
+    fn test_parse() {
+        use instructions::Instruction::*;
+        use day_18::instructions::Value::*;
+
+        assert_eq!(
+            instructions::parse("set b 57\nsub c -17000\nmul g e\njnz 1 -23"),
+            vec![
+                Set('b', Value(57)),
+                Subtract('c', Value(-17000)),
+                Multiply('g', Register('e')),
+                JumpNotZero(Value(1), Value(-23)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multiplies() {
+        // The inner loops run `(b - 2)^2` times, each of which has a single `mul`
+        assert_eq!(run(&instructions::parse(INPUT), Registers::new()).1, 55 * 55);
+    }
+
+    #[test]
+    fn test_optimised_matches_program() {
+        // Shrink the range so the unoptimised program finishes quickly
+        let program = instructions::parse(
+            &INPUT.replace("mul b 100", "mul b 1")
+                .replace("sub b -100000", "sub b 0")
+                .replace("sub c -17000", "sub c -170")
+        );
+
+        let mut registers = Registers::new();
+        registers.insert('a', 1);
+
+        let (registers, _) = run(&program, registers);
+
+        assert_eq!(optimised_h(&program).unwrap() as i64, registers[&'h']);
+        assert_eq!(optimised_h(&program), Ok(9));
+    }
+
+    #[test]
+    fn test_optimised_errors() {
+        let optimised = | input: &str | optimised_h(&instructions::parse(input));
+
+        assert_eq!(
+            optimised(&INPUT.replace("sub b -17", "sub b 17")),
+            Err(String::from("Expected `b` to increase at the end of the loop, but it changes by -17"))
+        );
+        assert_eq!(
+            optimised(&INPUT.replace("sub b -17", "sub b 0")),
+            Err(String::from("Expected `b` to increase at the end of the loop, but it changes by 0"))
+        );
+        assert_eq!(
+            optimised(&INPUT.replace("set f 1", "set f 2")),
+            Err(String::from("Unable to find the start of the main loop"))
+        );
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes: Vec<i64> = (0 .. 30).filter(| n | is_prime(*n)).collect();
+
+        assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A component with a port on each end, given by the number of pins on that port
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Component {
+    pub a: u32,
+    pub b: u32,
+}
+
+impl Component {
+    pub fn new(a: u32, b: u32) -> Self { Component { a, b } }
+
+    /// The strength of this component, which is the sum of both it's ports
+    pub fn strength(&self) -> u32 { self.a + self.b }
+
+    /// Given the port used to connect this component, returns the port left free on the other end
+    pub fn other_port(&self, port: u32) -> u32 {
+        if self.a == port { self.b } else { self.a }
+    }
+}
+
+impl fmt::Debug for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.a, self.b)
+    }
+}
+
+impl FromStr for Component {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.trim().split('/').collect();
+
+        if parts.len() != 2 {
+            return Err(format!("Expected two ports on component, got `{}`", s));
+        }
+
+        let a = parts[0].parse().map_err(| e | format!("Unable to parse port `{}`: {}", parts[0], e))?;
+        let b = parts[1].parse().map_err(| e | format!("Unable to parse port `{}`: {}", parts[1], e))?;
+
+        Ok(Component::new(a, b))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("0/2".parse(), Ok(Component::new(0, 2)));
+        assert_eq!(" 10/1 ".parse(), Ok(Component::new(10, 1)));
+        assert!("10".parse::<Component>().is_err());
+        assert!("a/1".parse::<Component>().is_err());
+    }
+
+    #[test]
+    fn test_other_port() {
+        let component = Component::new(3, 7);
+
+        assert_eq!(component.other_port(3), 7);
+        assert_eq!(component.other_port(7), 3);
+        assert_eq!(Component::new(2, 2).other_port(2), 2);
+    }
+}
@@ -0,0 +1,157 @@
+extern crate utils;
+
+mod component;
+
+use std::collections::HashMap;
+use component::Component;
+
+#[cfg(not(test))]
+fn main() {
+    let components = parse(&utils::read_all_input());
+
+    let strongest = strongest_bridge(&components);
+    println!("Part 1: {} ({:?})", strength(&strongest), strongest);
+
+    let longest = longest_bridge(&components);
+    println!("Part 2: {} ({:?})", strength(&longest), longest);
+}
+
+/// Parses the input into a list of components
+fn parse(input: &str) -> Vec<Component> {
+    input.trim().lines().map(| l | l.parse().unwrap()).collect()
+}
+
+/// The total strength of a bridge
+fn strength(bridge: &[Component]) -> u32 {
+    bridge.iter().map(| c | c.strength()).sum()
+}
+
+/// Finds the strongest bridge which can be built from the components
+fn strongest_bridge(components: &[Component]) -> Vec<Component> {
+    best_bridge(components, &| bridge | strength(bridge))
+}
+
+/// Finds the longest bridge which can be built from the components, using the strongest one if
+/// more than one bridge is the longest
+fn longest_bridge(components: &[Component]) -> Vec<Component> {
+    best_bridge(components, &| bridge | (bridge.len(), strength(bridge)))
+}
+
+/// Searches every bridge which can be built starting from a port with zero pins, and returns the
+/// one with the highest `score`
+///
+/// Components are returned in the order they are connected, as they were given in the input (so
+/// `0/1, 10/1, 9/10` is the bridge `0/1--10/1--9/10`)
+fn best_bridge<K, F>(components: &[Component], score: &F) -> Vec<Component>
+    where K: Ord, F: Fn(&[Component]) -> K
+{
+    // Index each component by the ports it has, so we only look at ones which can connect
+    let mut by_port: HashMap<u32, Vec<usize>> = HashMap::new();
+
+    for (i, component) in components.iter().enumerate() {
+        by_port.entry(component.a).or_default().push(i);
+
+        if component.a != component.b {
+            by_port.entry(component.b).or_default().push(i);
+        }
+    }
+
+    let mut search = Search {
+        components,
+        by_port,
+        used: vec![false; components.len()],
+        bridge: vec![],
+        best: (score(&[]), vec![]),
+        score,
+    };
+
+    search.extend(0);
+    search.best.1
+}
+
+/// The state of a backtracking search through the possible bridges
+struct Search<'a, K, F: 'a> {
+    components: &'a [Component],
+    by_port: HashMap<u32, Vec<usize>>,
+    used: Vec<bool>,
+    bridge: Vec<Component>,
+    best: (K, Vec<Component>),
+    score: &'a F,
+}
+
+impl<'a, K, F> Search<'a, K, F> where K: Ord, F: Fn(&[Component]) -> K {
+    /// Tries every unused component which fits on the free `port` at the end of the bridge
+    fn extend(&mut self, port: u32) {
+        let score = (self.score)(&self.bridge);
+
+        if score > self.best.0 {
+            self.best = (score, self.bridge.clone());
+        }
+
+        let candidates = match self.by_port.get(&port) {
+            Some(candidates) => candidates.clone(),
+            None             => return,
+        };
+
+        for i in candidates {
+            if self.used[i] {
+                continue;
+            }
+
+            let component = self.components[i];
+
+            self.used[i] = true;
+            self.bridge.push(component);
+
+            self.extend(component.other_port(port));
+
+            self.bridge.pop();
+            self.used[i] = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "
+        0/2
+        2/2
+        2/3
+        3/4
+        3/5
+        0/1
+        10/1
+        9/10
+    ";
+
+    #[test]
+    fn test_strongest_bridge() {
+        let bridge = strongest_bridge(&parse(INPUT));
+
+        assert_eq!(bridge, parse("0/1\n10/1\n9/10"));
+        assert_eq!(strength(&bridge), 31);
+    }
+
+    #[test]
+    fn test_longest_bridge() {
+        let bridge = longest_bridge(&parse(INPUT));
+
+        assert_eq!(bridge, parse("0/2\n2/2\n2/3\n3/5"));
+        assert_eq!(strength(&bridge), 19);
+    }
+
+    #[test]
+    fn test_duplicate_components() {
+        // Both copies of `1/1` can be used in the same bridge
+        let bridge = strongest_bridge(&parse("0/1\n1/1\n1/1\n5/6"));
+
+        assert_eq!(bridge, parse("0/1\n1/1\n1/1"));
+    }
+
+    #[test]
+    fn test_no_bridge() {
+        assert_eq!(strongest_bridge(&parse("1/2\n2/3")), vec![]);
+    }
+}
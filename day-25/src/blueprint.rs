@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Which way the cursor moves along the tape
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Movement {
+    Left,
+    Right,
+}
+
+/// What the machine does when it reads a given value in a given state
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Action {
+    pub write: bool,
+    pub movement: Movement,
+    pub next_state: usize,
+}
+
+/// The parsed blueprint of a Turing machine, where states are referred to by their index into
+/// `states`
+#[derive(Debug, PartialEq)]
+pub struct Blueprint {
+    pub start_state: usize,
+    pub steps: usize,
+
+    /// The actions for each state, when the current value is `0` and `1` respectively
+    pub states: Vec<[Action; 2]>,
+
+    /// The names of each state as given in the blueprint
+    pub names: Vec<String>,
+}
+
+/// Reads the next line, which must start with `prefix`, and returns the rest of the line without
+/// it's trailing `.` or `:`
+fn read_line<'a, I>(lines: &mut I, prefix: &str) -> Result<&'a str, String>
+    where I: Iterator<Item = &'a str>
+{
+    match lines.next() {
+        Some(line) if line.starts_with(prefix) =>
+            Ok(line[prefix.len() ..].trim_end_matches(['.', ':'])),
+        Some(line) =>
+            Err(format!("Expected line starting `{}`, got `{}`", prefix, line)),
+        None =>
+            Err(format!("Expected line starting `{}`, but the blueprint ended", prefix)),
+    }
+}
+
+/// Reads the three lines describing what to do when the given `value` is read
+fn read_action<'a, I>(lines: &mut I, value: &str) -> Result<(bool, Movement, &'a str), String>
+    where I: Iterator<Item = &'a str>
+{
+    read_line(lines, "If the current value is ").and_then(| v |
+        if v == value { Ok(()) } else { Err(format!("Expected the action for {}, got {}", value, v)) }
+    )?;
+
+    let write = match read_line(lines, "- Write the value ")? {
+        "0" => false,
+        "1" => true,
+        v   => return Err(format!("Unable to write value `{}`", v)),
+    };
+
+    let movement = match read_line(lines, "- Move one slot to the ")? {
+        "left"  => Movement::Left,
+        "right" => Movement::Right,
+        m       => return Err(format!("Unknown direction `{}`", m)),
+    };
+
+    let next_state = read_line(lines, "- Continue with state ")?;
+
+    Ok((write, movement, next_state))
+}
+
+impl FromStr for Blueprint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(| l | l.trim()).filter(| l | !l.is_empty()).peekable();
+
+        let start_name = read_line(&mut lines, "Begin in state ")?;
+        let steps = read_line(&mut lines, "Perform a diagnostic checksum after ")?
+            .trim_end_matches(" steps")
+            .parse()
+            .map_err(| e | format!("Unable to parse number of steps: {}", e))?;
+
+        // Read each state, leaving the next state names to be resolved once all states are known
+        let mut names: Vec<String> = vec![];
+        let mut unresolved = vec![];
+
+        while lines.peek().is_some() {
+            let name = read_line(&mut lines, "In state ")?;
+
+            if names.iter().any(| n | n == name) {
+                return Err(format!("State `{}` is defined more than once", name));
+            }
+
+            names.push(String::from(name));
+            unresolved.push([read_action(&mut lines, "0")?, read_action(&mut lines, "1")?]);
+        }
+
+        let indexes: HashMap<&str, usize> =
+            names.iter().enumerate().map(| (i, n) | (n.as_str(), i)).collect();
+
+        let index_of = | name: &str | {
+            indexes.get(name).cloned().ok_or_else(|| format!("Unknown state `{}`", name))
+        };
+
+        let mut states = vec![];
+
+        for actions in unresolved {
+            let mut resolved = [Action { write: false, movement: Movement::Left, next_state: 0 }; 2];
+
+            for (i, &(write, movement, next)) in actions.iter().enumerate() {
+                resolved[i] = Action { write, movement, next_state: index_of(next)? };
+            }
+
+            states.push(resolved);
+        }
+
+        Ok(Blueprint { start_state: index_of(start_name)?, steps, states, names })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let blueprint: Blueprint = "
+            Begin in state B.
+            Perform a diagnostic checksum after 12 steps.
+
+            In state A:
+              If the current value is 0:
+                - Write the value 1.
+                - Move one slot to the right.
+                - Continue with state B.
+              If the current value is 1:
+                - Write the value 0.
+                - Move one slot to the left.
+                - Continue with state A.
+
+            In state B:
+              If the current value is 0:
+                - Write the value 0.
+                - Move one slot to the left.
+                - Continue with state A.
+              If the current value is 1:
+                - Write the value 1.
+                - Move one slot to the right.
+                - Continue with state B.
+        ".parse().unwrap();
+
+        assert_eq!(blueprint.start_state, 1);
+        assert_eq!(blueprint.steps, 12);
+        assert_eq!(blueprint.names, vec!["A", "B"]);
+        assert_eq!(
+            blueprint.states[0],
+            [
+                Action { write: true,  movement: Movement::Right, next_state: 1 },
+                Action { write: false, movement: Movement::Left,  next_state: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "Begin in state A.\nPerform a diagnostic checksum after 1 steps.\nIn state A:"
+                .parse::<Blueprint>(),
+            Err(String::from("Expected line starting `If the current value is `, but the blueprint ended"))
+        );
+
+        assert_eq!(
+            "Begin in state C.\nPerform a diagnostic checksum after 1 steps.".parse::<Blueprint>(),
+            Err(String::from("Unknown state `C`"))
+        );
+    }
+}
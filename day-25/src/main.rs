@@ -0,0 +1,96 @@
+extern crate utils;
+
+mod blueprint;
+mod tape;
+
+use blueprint::Blueprint;
+use tape::Tape;
+
+#[cfg(not(test))]
+fn main() {
+    let blueprint: Blueprint = utils::read_all_input().parse().unwrap();
+
+    println!("Part 1: {}", run(&blueprint).checksum());
+}
+
+/// Runs the Turing machine described by the `blueprint` for it's diagnostic number of steps and
+/// returns the resulting tape
+fn run(blueprint: &Blueprint) -> Tape {
+    let mut tape = Tape::default();
+    let mut state = blueprint.start_state;
+
+    for _ in 0 .. blueprint.steps {
+        let action = &blueprint.states[state][tape.read() as usize];
+
+        tape.write(action.write);
+        tape.mov(action.movement);
+        state = action.next_state;
+    }
+
+    tape
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "
+        Begin in state A.
+        Perform a diagnostic checksum after 6 steps.
+
+        In state A:
+          If the current value is 0:
+            - Write the value 1.
+            - Move one slot to the right.
+            - Continue with state B.
+          If the current value is 1:
+            - Write the value 0.
+            - Move one slot to the left.
+            - Continue with state B.
+
+        In state B:
+          If the current value is 0:
+            - Write the value 1.
+            - Move one slot to the left.
+            - Continue with state A.
+          If the current value is 1:
+            - Write the value 1.
+            - Move one slot to the right.
+            - Continue with state A.
+    ";
+
+    #[test]
+    fn test_example() {
+        let blueprint: Blueprint = INPUT.parse().unwrap();
+
+        assert_eq!(run(&blueprint).checksum(), 3);
+    }
+
+    #[test]
+    fn test_many_steps() {
+        use std::collections::HashMap;
+        use blueprint::Movement;
+
+        let blueprint: Blueprint = INPUT.replace("after 6 steps", "after 1000000 steps")
+            .parse().unwrap();
+
+        // Run the same machine on a simple (but slow) sparse tape to compare against
+        let mut slots: HashMap<i64, bool> = HashMap::new();
+        let mut cursor = 0;
+        let mut state = blueprint.start_state;
+
+        for _ in 0 .. blueprint.steps {
+            let value = slots.get(&cursor).cloned().unwrap_or(false);
+            let action = &blueprint.states[state][value as usize];
+
+            slots.insert(cursor, action.write);
+            cursor += if action.movement == Movement::Left { -1 } else { 1 };
+            state = action.next_state;
+        }
+
+        assert_eq!(
+            run(&blueprint).checksum(),
+            slots.values().filter(| v | **v).count()
+        );
+    }
+}
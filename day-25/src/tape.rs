@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+
+use blueprint::Movement;
+
+/// An infinite tape of `0`/`1` values, only storing the slots which have been visited
+///
+/// The tape grows by one slot at whichever end the cursor moves off, so it stays a single
+/// contiguous block no matter which direction the machine wanders in.
+pub struct Tape {
+    slots: VecDeque<bool>,
+    cursor: usize,
+    ones: usize,
+}
+
+impl Default for Tape {
+    fn default() -> Self {
+        let mut slots = VecDeque::new();
+        slots.push_back(false);
+
+        Tape { slots, cursor: 0, ones: 0 }
+    }
+}
+
+impl Tape {
+    /// The value under the cursor
+    pub fn read(&self) -> bool { self.slots[self.cursor] }
+
+    /// Writes a value to the slot under the cursor
+    pub fn write(&mut self, value: bool) {
+        let slot = &mut self.slots[self.cursor];
+
+        if *slot != value {
+            if value { self.ones += 1; } else { self.ones -= 1; }
+            *slot = value;
+        }
+    }
+
+    /// Moves the cursor one slot along the tape
+    pub fn mov(&mut self, movement: Movement) {
+        match movement {
+            Movement::Left => {
+                if self.cursor == 0 {
+                    self.slots.push_front(false);
+                } else {
+                    self.cursor -= 1;
+                }
+            },
+            Movement::Right => {
+                self.cursor += 1;
+
+                if self.cursor == self.slots.len() {
+                    self.slots.push_back(false);
+                }
+            },
+        }
+    }
+
+    /// The number of `1`s on the tape
+    pub fn checksum(&self) -> usize { self.ones }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tape() {
+        let mut tape = Tape::default();
+
+        tape.write(true);
+        tape.mov(Movement::Left);
+        tape.mov(Movement::Left);
+        tape.write(true);
+        tape.mov(Movement::Right);
+        tape.mov(Movement::Right);
+        assert!(tape.read());
+
+        tape.mov(Movement::Right);
+        assert!(!tape.read());
+
+        assert_eq!(tape.checksum(), 2);
+        assert_eq!(tape.slots.len(), 4);
+
+        tape.mov(Movement::Left);
+        tape.write(false);
+        assert_eq!(tape.checksum(), 1);
+    }
+}
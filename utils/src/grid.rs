@@ -0,0 +1,117 @@
+/// A position on an infinite grid, where `x` increases to the right and `y` increases upwards
+#[derive(Hash, Eq, PartialEq, Default, Clone, Copy, Debug)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A direction of travel around the grid
+#[derive(Hash, Eq, PartialEq, Default, Clone, Copy, Debug)]
+pub enum Direction {
+    Left,
+    #[default]
+    Right,
+    Down,
+    Up,
+}
+
+impl Direction {
+    /// The direction after turning 90 degrees anti-clockwise
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Left  => Direction::Down,
+            Direction::Right => Direction::Up,
+            Direction::Down  => Direction::Right,
+            Direction::Up    => Direction::Left,
+        }
+    }
+
+    /// The direction after turning 90 degrees clockwise
+    pub fn turn_right(self) -> Direction {
+        self.turn_left().reverse()
+    }
+
+    /// The direction after turning around
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::Left  => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Down  => Direction::Up,
+            Direction::Up    => Direction::Down,
+        }
+    }
+}
+
+impl Position {
+    pub fn new(x: i64, y: i64) -> Self { Position { x, y } }
+
+    /// Returns the position one step away in the given `direction`
+    pub fn step(&self, direction: Direction) -> Position {
+        match direction {
+            Direction::Left  => self.left(),
+            Direction::Right => self.right(),
+            Direction::Down  => self.down(),
+            Direction::Up    => self.up(),
+        }
+    }
+
+    /// All eight positions surrounding this one (including diagonals)
+    pub fn neighbours(&self) -> [Position; 8] {
+        [
+            self.left(),
+            self.left().up(),
+            self.up(),
+            self.right().up(),
+            self.right(),
+            self.right().down(),
+            self.down(),
+            self.left().down()
+        ]
+    }
+
+    /// The four positions directly above, below, left and right of this one
+    pub fn orthogonal_neighbours(&self) -> [Position; 4] {
+        [self.left(), self.up(), self.right(), self.down()]
+    }
+
+    pub fn left(&self) -> Position {
+        Position { x: self.x - 1, y: self.y }
+    }
+
+    pub fn right(&self) -> Position {
+        Position { x: self.x + 1, y: self.y }
+    }
+
+    pub fn down(&self) -> Position {
+        Position { x: self.x, y: self.y - 1 }
+    }
+
+    pub fn up(&self) -> Position {
+        Position { x: self.x, y: self.y + 1 }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_turning() {
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Down.reverse(), Direction::Up);
+
+        for direction in &[Direction::Left, Direction::Right, Direction::Down, Direction::Up] {
+            assert_eq!(direction.turn_left().turn_right(), *direction);
+        }
+    }
+
+    #[test]
+    fn test_step() {
+        let origin = Position::default();
+
+        assert_eq!(origin.step(Direction::Up), Position::new(0, 1));
+        assert_eq!(origin.step(Direction::Left).step(Direction::Down), Position::new(-1, -1));
+    }
+}
@@ -1,6 +1,7 @@
 use std::io;
 
 pub mod knot_hash;
+pub mod grid;
 
 /// Reads a single line input from std::in and casts it to the correct type
 pub fn read_input<T: std::str::FromStr>() -> T
@@ -14,3 +15,16 @@ pub fn read_input<T: std::str::FromStr>() -> T
 
     input.trim().parse().expect("Unable to cast input")
 }
+
+/// Reads everything from std::in until EOF, for inputs which span multiple lines
+pub fn read_all_input() -> String {
+    use std::io::Read;
+
+    eprintln!("Please enter the input (end with EOF): ");
+
+    let mut input = String::new();
+
+    io::stdin().read_to_string(&mut input).expect("Failed to read input");
+
+    input
+}